@@ -63,6 +63,10 @@ enum Command {
     /// # Example (with code formatting)
     ///
     /// `subxt codegen | rustfmt --edition=2018 --emit=stdout`
+    ///
+    /// # Example (split into a file per pallet)
+    ///
+    /// `subxt codegen --output-dir src/api && rustfmt --edition=2018 src/api/mod.rs`
     Codegen {
         /// the url of the substrate node to query for metadata for codegen.
         #[structopt(name = "url", long, parse(try_from_str))]
@@ -70,6 +74,10 @@ enum Command {
         /// the path to the encoded metadata file.
         #[structopt(short, long, parse(from_os_str))]
         file: Option<PathBuf>,
+        /// the directory to write the generated code to, with the module of each pallet in a
+        /// file of its own, instead of printing it.
+        #[structopt(long, parse(from_os_str))]
+        output_dir: Option<PathBuf>,
     },
 }
 
@@ -102,7 +110,11 @@ fn main() -> color_eyre::Result<()> {
                 }
             }
         }
-        Command::Codegen { url, file } => {
+        Command::Codegen {
+            url,
+            file,
+            output_dir,
+        } => {
             if let Some(file) = file.as_ref() {
                 if url.is_some() {
                     eyre::bail!("specify one of `--url` or `--file` but not both")
@@ -111,7 +123,7 @@ fn main() -> color_eyre::Result<()> {
                 let mut file = fs::File::open(file)?;
                 let mut bytes = Vec::new();
                 file.read_to_end(&mut bytes)?;
-                codegen(&mut &bytes[..])?;
+                codegen(&mut &bytes[..], output_dir)?;
                 return Ok(())
             }
 
@@ -119,7 +131,7 @@ fn main() -> color_eyre::Result<()> {
                 url::Url::parse("http://localhost:9933").expect("default url is valid")
             });
             let (_, bytes) = fetch_metadata(&url)?;
-            codegen(&mut &bytes[..])?;
+            codegen(&mut &bytes[..], output_dir)?;
             return Ok(())
         }
     }
@@ -145,12 +157,26 @@ fn fetch_metadata(url: &url::Url) -> color_eyre::Result<(String, Vec<u8>)> {
     Ok((hex_data, bytes))
 }
 
-fn codegen<I: Input>(encoded: &mut I) -> color_eyre::Result<()> {
+fn codegen<I: Input>(
+    encoded: &mut I,
+    output_dir: Option<PathBuf>,
+) -> color_eyre::Result<()> {
     let metadata = <RuntimeMetadataPrefixed as Decode>::decode(encoded)?;
     let generator = subxt_codegen::RuntimeGenerator::new(metadata);
     let item_mod = syn::parse_quote!(
         pub mod api {}
     );
+    if let Some(output_dir) = output_dir {
+        fs::create_dir_all(&output_dir)?;
+        for (name, contents) in
+            generator.generate_runtime_files(item_mod, Default::default())
+        {
+            let path = output_dir.join(name);
+            fs::write(&path, contents.to_string())
+                .with_context(|| format!("error writing {}", path.display()))?;
+        }
+        return Ok(())
+    }
     let runtime_api = generator.generate_runtime(item_mod, Default::default());
     println!("{}", runtime_api);
     Ok(())
@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::types::TypeGenerator;
+use crate::types::{
+    TypeGenerator,
+    TypeParameter,
+};
 use frame_metadata::{
     PalletCallMetadata,
     PalletMetadata,
@@ -36,10 +39,15 @@ pub fn generate_calls(
 ) -> TokenStream2 {
     let struct_defs =
         super::generate_structs_from_variants(type_gen, call.ty.id(), "Call");
+    let call_variants = match type_gen.resolve_type(call.ty.id()).type_def() {
+        scale_info::TypeDef::Variant(variant) => variant.variants().to_vec(),
+        _ => abort_call_site!("Call type should be a variant/enum type"),
+    };
     let (call_structs, call_fns): (Vec<_>, Vec<_>) = struct_defs
-        .iter()
-        .map(|struct_def| {
-            let (call_fn_args, call_args): (Vec<_>, Vec<_>) = struct_def
+        .into_iter()
+        .zip(call_variants.iter())
+        .map(|(mut struct_def, call_variant)| {
+            let fields = struct_def
                 .named_fields()
                 .unwrap_or_else(|| {
                     abort_call_site!(
@@ -47,37 +55,108 @@ pub fn generate_calls(
                         call.ty.id()
                     )
                 })
+                .to_vec();
+            // account addresses are of the address type of the config, so that the calls
+            // can be submitted with other address types than the one of the metadata, and
+            // the transaction api takes anything converting into it
+            let address_fields = fields
                 .iter()
-                .map(|(name, ty)| (quote!( #name: #ty ), name))
+                .zip(call_variant.fields())
+                .filter(|(_, field)| is_address(type_gen, field.ty().id()))
+                .map(|((name, _), field)| (name, field.ty().id()))
+                .collect::<Vec<_>>();
+            for (name, type_id) in &address_fields {
+                let param = TypeParameter::new(*type_id, format_ident!("Address"));
+                struct_def.make_field_generic(name, param);
+            }
+            let is_address_field = |name: &syn::Ident| {
+                address_fields.iter().any(|(field, _)| *field == name)
+            };
+            let (client_fn_args, client_call_args): (Vec<_>, Vec<_>) = fields
+                .iter()
+                .map(|(name, ty)| {
+                    if is_address_field(name) {
+                        (
+                            quote!( #name: impl ::core::convert::Into<T::Address> ),
+                            quote!( #name: #name.into() ),
+                        )
+                    } else {
+                        (quote!( #name: #ty ), quote!( #name ))
+                    }
+                })
+                .unzip();
+            // the `Call` of the metadata takes the address type of the metadata, but the
+            // addresses convert into it like into the address type of the config
+            let (call_fn_args, call_args): (Vec<_>, Vec<_>) = fields
+                .iter()
+                .map(|(name, ty)| {
+                    if is_address_field(name) {
+                        (
+                            quote!( #name: impl ::core::convert::Into<#ty> ),
+                            quote!( #name: #name.into() ),
+                        )
+                    } else {
+                        (quote!( #name: #ty ), quote!( #name ))
+                    }
+                })
                 .unzip();
 
             let pallet_name = &pallet.name;
             let call_struct_name = &struct_def.name;
             let function_name = struct_def.name.to_string().to_snake_case();
             let fn_name = format_ident!("{}", function_name);
+            let call_fn_name = format_ident!("{}_call", function_name);
+            let variant_name = format_ident!("{}", call_variant.name());
+            let (call_impl_params, call_struct_ty, client_call_ty) =
+                if address_fields.is_empty() {
+                    (
+                        quote!(),
+                        quote!( #call_struct_name ),
+                        quote!( #call_struct_name ),
+                    )
+                } else {
+                    (
+                        quote!( <Address: ::subxt::codec::Encode> ),
+                        quote!( #call_struct_name<Address> ),
+                        quote!( #call_struct_name<T::Address> ),
+                    )
+                };
+            let call_hash =
+                subxt_metadata::get_call_hash(type_gen.type_registry(), call_variant)
+                    .to_vec();
 
             let call_struct = quote! {
                 #struct_def
 
-                impl ::subxt::Call for #call_struct_name {
+                impl #call_impl_params ::subxt::Call for #call_struct_ty {
                     const PALLET: &'static str = #pallet_name;
                     const FUNCTION: &'static str = #function_name;
+                    const CALL_HASH: [u8; 32] = [ #( #call_hash, )* ];
                 }
             };
             let client_fn = quote! {
                 pub fn #fn_name(
                     &self,
-                    #( #call_fn_args, )*
-                ) -> ::subxt::SubmittableExtrinsic<T, #call_struct_name> {
-                    let call = #call_struct_name { #( #call_args, )* };
+                    #( #client_fn_args, )*
+                ) -> ::subxt::SubmittableExtrinsic<T, #client_call_ty> {
+                    let call = #call_struct_name { #( #client_call_args, )* };
                     ::subxt::SubmittableExtrinsic::new(self.client, call)
                 }
+
+                pub fn #call_fn_name(
+                    &self,
+                    #( #call_fn_args, )*
+                ) -> super::Call {
+                    super::Call::#variant_name { #( #call_args, )* }
+                }
             };
             (call_struct, client_fn)
         })
         .unzip();
+    let call_type = type_gen.resolve_type_path(call.ty.id(), &[]);
 
     quote! {
+        pub type Call = #call_type;
         pub mod calls {
             use super::#types_mod_ident;
             #( #call_structs )*
@@ -99,3 +178,9 @@ pub fn generate_calls(
         }
     }
 }
+
+/// Returns `true` if the type with the given id is the `MultiAddress` of `sp_runtime`.
+fn is_address(type_gen: &TypeGenerator, type_id: u32) -> bool {
+    let ty = type_gen.resolve_type(type_id);
+    ty.path().segments().join("::") == "sp_runtime::multiaddress::MultiAddress"
+}
@@ -0,0 +1,66 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde_json::Value as JsonValue;
+use tungstenite::Message;
+
+/// Fetches the SCALE encoded metadata from the node at the given url with `state_getMetadata`.
+pub fn fetch_metadata_bytes(url: &str) -> Result<Vec<u8>, String> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "state_getMetadata",
+        "params": [],
+        "id": 1
+    });
+
+    let response = if url.starts_with("ws://") || url.starts_with("wss://") {
+        request_over_ws(url, request)?
+    } else if url.starts_with("http://") || url.starts_with("https://") {
+        request_over_http(url, request)?
+    } else {
+        return Err("expected a ws://, wss://, http:// or https:// url".into())
+    };
+
+    let hex_data = response["result"]
+        .as_str()
+        .ok_or_else(|| format!("unexpected response: {}", response))?;
+    hex::decode(hex_data.trim_start_matches("0x")).map_err(|e| e.to_string())
+}
+
+fn request_over_ws(url: &str, request: JsonValue) -> Result<JsonValue, String> {
+    let (mut socket, _) = tungstenite::connect(url).map_err(|e| e.to_string())?;
+    socket
+        .write_message(Message::Text(request.to_string()))
+        .map_err(|e| e.to_string())?;
+    let response = loop {
+        match socket.read_message().map_err(|e| e.to_string())? {
+            Message::Text(text) => break text,
+            Message::Close(_) => return Err("connection closed by the node".into()),
+            _ => continue,
+        }
+    };
+    let _ = socket.close(None);
+    serde_json::from_str(&response).map_err(|e| e.to_string())
+}
+
+fn request_over_http(url: &str, request: JsonValue) -> Result<JsonValue, String> {
+    ureq::post(url)
+        .set("Content-Type", "application/json")
+        .send_json(request)
+        .map_err(|e| e.to_string())?
+        .into_json()
+        .map_err(|e| e.to_string())
+}
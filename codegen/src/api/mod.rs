@@ -15,8 +15,12 @@
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
 mod calls;
+mod constants;
 mod events;
+mod fetch_metadata;
 mod storage;
+#[cfg(test)]
+mod tests;
 
 use super::GeneratedTypeDerives;
 use crate::{
@@ -26,7 +30,11 @@ use crate::{
 };
 use codec::Decode;
 use frame_metadata::{
-    v14::RuntimeMetadataV14,
+    v14::{
+        PalletMetadata,
+        RuntimeMetadataV14,
+        StorageEntryType,
+    },
     RuntimeMetadata,
     RuntimeMetadataPrefixed,
 };
@@ -37,6 +45,7 @@ use quote::{
     format_ident,
     quote,
 };
+use scale_info::form::PortableForm;
 use std::{
     collections::HashMap,
     fs,
@@ -44,15 +53,13 @@ use std::{
     path,
     string::ToString,
 };
-use syn::{
-    parse_quote,
-    punctuated::Punctuated,
-};
+use syn::parse_quote;
 
 pub fn generate_runtime_api<P>(
     item_mod: syn::ItemMod,
     path: P,
-    generated_type_derives: Option<Punctuated<syn::Path, syn::Token![,]>>,
+    derives: GeneratedTypeDerives,
+    pallets: Option<Vec<String>>,
 ) -> TokenStream2
 where
     P: AsRef<path::Path>,
@@ -65,35 +72,207 @@ where
     file.read_to_end(&mut bytes)
         .unwrap_or_else(|e| abort_call_site!("Failed to read metadata file: {}", e));
 
+    generate_runtime_api_from_bytes(item_mod, &bytes, derives, pallets)
+}
+
+/// Generates the runtime API from the metadata fetched from the node at the given `ws://`,
+/// `wss://`, `http://` or `https://` url.
+pub fn generate_runtime_api_from_url(
+    item_mod: syn::ItemMod,
+    url: &str,
+    derives: GeneratedTypeDerives,
+    pallets: Option<Vec<String>>,
+) -> TokenStream2 {
+    let bytes = fetch_metadata::fetch_metadata_bytes(url).unwrap_or_else(|e| {
+        abort_call_site!(
+            "Failed to fetch metadata from {}: {}. Make sure the node is running, or use \
+             `runtime_metadata_path` to generate from a metadata file when building offline",
+            url,
+            e
+        )
+    });
+
+    generate_runtime_api_from_bytes(item_mod, &bytes, derives, pallets)
+}
+
+fn generate_runtime_api_from_bytes(
+    item_mod: syn::ItemMod,
+    bytes: &[u8],
+    derives: GeneratedTypeDerives,
+    pallets: Option<Vec<String>>,
+) -> TokenStream2 {
     let metadata = frame_metadata::RuntimeMetadataPrefixed::decode(&mut &bytes[..])
         .unwrap_or_else(|e| abort_call_site!("Failed to decode metadata: {}", e));
 
-    let mut derives = GeneratedTypeDerives::default();
-    if let Some(user_derives) = generated_type_derives {
-        derives.append(user_derives.iter().cloned())
+    let mut generator = RuntimeGenerator::new(metadata);
+    if let Some(pallets) = pallets {
+        generator.generate_pallets(pallets);
     }
-
-    let generator = RuntimeGenerator::new(metadata);
     generator.generate_runtime(item_mod, derives)
 }
 
 pub struct RuntimeGenerator {
     metadata: RuntimeMetadataV14,
+    /// The names of the pallets to generate, or `None` to generate all pallets.
+    pallets: Option<Vec<String>>,
 }
 
 impl RuntimeGenerator {
     pub fn new(metadata: RuntimeMetadataPrefixed) -> Self {
         match metadata.1 {
-            RuntimeMetadata::V14(v14) => Self { metadata: v14 },
+            RuntimeMetadata::V14(v14) => {
+                Self {
+                    metadata: v14,
+                    pallets: None,
+                }
+            }
             _ => panic!("Unsupported metadata version {:?}", metadata.1),
         }
     }
 
+    /// Restrict the generated API to the pallets with the given names, e.g. to reduce compile
+    /// times for large runtimes.
+    ///
+    /// Only the `runtime_types` referenced by those pallets are generated. The outer `Call`
+    /// enum references the calls of all pallets, so it is omitted unless all pallets with
+    /// calls are generated, or the generated pallets reference it, e.g. `Utility::batch`.
+    /// The `System` pallet is required by the generated `DefaultConfig`.
+    pub fn generate_pallets(&mut self, pallets: Vec<String>) {
+        for pallet in &pallets {
+            if !self.metadata.pallets.iter().any(|p| &p.name == pallet) {
+                abort_call_site!("Pallet `{}` not found in the metadata", pallet)
+            }
+        }
+        if !pallets.iter().any(|pallet| pallet == "System") {
+            abort_call_site!("The `System` pallet is required by the generated API")
+        }
+        self.pallets = Some(pallets);
+    }
+
+    fn is_generated(&self, pallet: &PalletMetadata<PortableForm>) -> bool {
+        match self.pallets {
+            Some(ref pallets) => pallets.iter().any(|name| name == &pallet.name),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if the outer `Call` enum of the runtime is generated along with a subset
+    /// of the pallets, which is the case if all pallets with calls are generated, or if the
+    /// generated pallets reference it anyway, e.g. `Utility::batch`.
+    fn generates_outer_call(
+        &self,
+        type_gen: &TypeGenerator,
+        pallet_type_ids: &[u32],
+        call_ty: u32,
+    ) -> bool {
+        self.metadata
+            .pallets
+            .iter()
+            .filter(|pallet| pallet.calls.is_some())
+            .all(|pallet| self.is_generated(pallet))
+            || type_gen
+                .referenced_type_ids(pallet_type_ids.iter().copied())
+                .contains(&call_ty)
+    }
+
+    /// Returns the ids of the types referenced by the generated pallets.
+    fn pallet_type_ids(&self) -> Vec<u32> {
+        let mut ids = Vec::new();
+        for pallet in self
+            .metadata
+            .pallets
+            .iter()
+            .filter(|p| self.is_generated(p))
+        {
+            ids.extend(pallet.calls.as_ref().map(|calls| calls.ty.id()));
+            ids.extend(pallet.event.as_ref().map(|event| event.ty.id()));
+            ids.extend(pallet.error.as_ref().map(|error| error.ty.id()));
+            ids.extend(pallet.constants.iter().map(|constant| constant.ty.id()));
+            if let Some(ref storage) = pallet.storage {
+                for entry in &storage.entries {
+                    match entry.ty {
+                        StorageEntryType::Plain(ref ty) => ids.push(ty.id()),
+                        StorageEntryType::Map {
+                            ref key, ref value, ..
+                        } => ids.extend([key.id(), value.id()]),
+                    }
+                }
+            }
+        }
+        ids
+    }
+
     pub fn generate_runtime(
         &self,
         item_mod: syn::ItemMod,
         derives: GeneratedTypeDerives,
     ) -> TokenStream2 {
+        let RuntimeParts {
+            mod_ident,
+            outer_items,
+            modules,
+            api_items,
+        } = self.generate_runtime_parts(item_mod, derives);
+        let modules = modules.iter().map(|(name, items)| {
+            quote! {
+                pub mod #name {
+                    #items
+                }
+            }
+        });
+
+        quote! {
+            #[allow(dead_code, unused_imports, non_camel_case_types)]
+            pub mod #mod_ident {
+                #outer_items
+                #( #modules )*
+                #api_items
+            }
+        }
+    }
+
+    /// Generates the runtime API split into files, with the module of each pallet and the
+    /// `runtime_types` module in a file of its own.
+    ///
+    /// Regenerating the API for an upgraded runtime then only changes the files of the
+    /// changed pallets, so the compiler can reuse the incremental results of the others,
+    /// which are invalidated when the spans of a single file shift.
+    ///
+    /// Returns the name and contents of each file, starting with the root `mod.rs` which
+    /// declares the other modules.
+    pub fn generate_runtime_files(
+        &self,
+        item_mod: syn::ItemMod,
+        derives: GeneratedTypeDerives,
+    ) -> Vec<(String, TokenStream2)> {
+        let RuntimeParts {
+            outer_items,
+            modules,
+            api_items,
+            ..
+        } = self.generate_runtime_parts(item_mod, derives);
+        let names = modules.iter().map(|(name, _)| name);
+        let root = quote! {
+            #![allow(dead_code, unused_imports, non_camel_case_types)]
+            #outer_items
+            #( pub mod #names; )*
+            #api_items
+        };
+
+        let mut files = vec![("mod.rs".to_string(), root)];
+        files.extend(
+            modules
+                .into_iter()
+                .map(|(name, items)| (format!("{}.rs", name), items)),
+        );
+        files
+    }
+
+    fn generate_runtime_parts(
+        &self,
+        item_mod: syn::ItemMod,
+        derives: GeneratedTypeDerives,
+    ) -> RuntimeParts {
         let item_mod_ir = ir::ItemMod::from(item_mod);
 
         // some hardcoded default type substitutes, can be overridden by user
@@ -139,12 +318,35 @@ impl RuntimeGenerator {
             type_substitutes,
             derives.clone(),
         );
-        let types_mod = type_gen.generate_types_mod();
+        let extrinsic_ty = type_gen.resolve_type(self.metadata.extrinsic.ty.id());
+        let call_ty = extrinsic_ty
+            .type_params()
+            .iter()
+            .find(|param| param.name() == "Call")
+            .and_then(|param| param.ty())
+            .unwrap_or_else(|| {
+                abort_call_site!("Extrinsic type should have a `Call` type parameter")
+            })
+            .id();
+        let pallet_type_ids = self.pallet_type_ids();
+        let generate_outer_call = self.pallets.is_none()
+            || self.generates_outer_call(&type_gen, &pallet_type_ids, call_ty);
+        let types_mod = match self.pallets {
+            Some(_) => {
+                let mut type_ids = pallet_type_ids;
+                if generate_outer_call {
+                    type_ids.push(call_ty);
+                }
+                type_gen.generate_types_mod_for(type_ids)
+            }
+            None => type_gen.generate_types_mod(),
+        };
         let types_mod_ident = types_mod.ident();
         let pallets_with_mod_names = self
             .metadata
             .pallets
             .iter()
+            .filter(|pallet| self.is_generated(pallet))
             .map(|pallet| {
                 (
                     pallet,
@@ -171,17 +373,40 @@ impl RuntimeGenerator {
                 quote!()
             };
 
-            quote! {
-                pub mod #mod_name {
-                    use super::#types_mod_ident;
-                    #calls
-                    #event
-                    #storage_mod
+            let constants_mod = if !pallet.constants.is_empty() {
+                constants::generate_constants(
+                    &type_gen,
+                    pallet,
+                    &pallet.constants,
+                    types_mod_ident,
+                )
+            } else {
+                quote!()
+            };
+
+            let error = if let Some(ref error) = pallet.error {
+                let error_type = type_gen.resolve_type_path(error.ty.id(), &[]);
+                quote! {
+                    pub type Error = #error_type;
                 }
-            }
+            } else {
+                quote!()
+            };
+
+            let items = quote! {
+                use super::#types_mod_ident;
+                #calls
+                #event
+                #error
+                #storage_mod
+                #constants_mod
+            };
+            ((*mod_name).clone(), items)
         });
+        let mut modules = modules.collect::<Vec<_>>();
+        modules.push((types_mod_ident.clone(), types_mod.items()));
 
-        let outer_event_variants = self.metadata.pallets.iter().filter_map(|p| {
+        let outer_event_variants = pallets_with_mod_names.iter().filter_map(|(p, _)| {
             let variant_name = format_ident!("{}", p.name);
             let mod_name = format_ident!("{}", p.name.to_string().to_snake_case());
             let index = proc_macro2::Literal::u8_unsuffixed(p.index);
@@ -195,12 +420,45 @@ impl RuntimeGenerator {
         });
 
         let outer_event = quote! {
+            /// The outer event enum of the runtime, with a variant for each pallet.
             #derives
             pub enum Event {
                 #( #outer_event_variants )*
             }
         };
 
+        let outer_error_variants = pallets_with_mod_names.iter().filter_map(|(p, _)| {
+            let variant_name = format_ident!("{}", p.name);
+            let mod_name = format_ident!("{}", p.name.to_string().to_snake_case());
+            let index = proc_macro2::Literal::u8_unsuffixed(p.index);
+
+            p.error.as_ref().map(|_| {
+                quote! {
+                    #[codec(index = #index)]
+                    #variant_name(#mod_name::Error),
+                }
+            })
+        });
+
+        let outer_error = quote! {
+            /// The outer error enum of the runtime, with a variant for each pallet, into which
+            /// a [`::subxt::PalletError`] can be decoded.
+            #derives
+            pub enum Error {
+                #( #outer_error_variants )*
+            }
+        };
+
+        let outer_call = if generate_outer_call {
+            let call_type = type_gen.resolve_type_path(call_ty, &[]);
+            quote! {
+                /// The outer call enum of the runtime, with a variant for each pallet.
+                pub type Call = #call_type;
+            }
+        } else {
+            quote!()
+        };
+
         let mod_ident = item_mod_ir.ident;
         let pallets_with_storage =
             pallets_with_mod_names
@@ -214,111 +472,140 @@ impl RuntimeGenerator {
                 .filter_map(|(pallet, pallet_mod_name)| {
                     pallet.calls.as_ref().map(|_| pallet_mod_name)
                 });
+        let pallets_with_constants = pallets_with_mod_names
+            .iter()
+            .filter(|(pallet, _)| !pallet.constants.is_empty())
+            .map(|(_, pallet_mod_name)| pallet_mod_name);
 
-        quote! {
-            #[allow(dead_code, unused_imports, non_camel_case_types)]
-            pub mod #mod_ident {
-                #outer_event
-                #( #modules )*
-                #types_mod
-
-                /// Default configuration of common types for a target Substrate runtime.
-                #[derive(Clone, Debug, Default, Eq, PartialEq)]
-                pub struct DefaultConfig;
-
-                impl ::subxt::Config for DefaultConfig {
-                    type Index = u32;
-                    type BlockNumber = u32;
-                    type Hash = ::subxt::sp_core::H256;
-                    type Hashing = ::subxt::sp_runtime::traits::BlakeTwo256;
-                    type AccountId = ::subxt::sp_runtime::AccountId32;
-                    type Address = ::subxt::sp_runtime::MultiAddress<Self::AccountId, u32>;
-                    type Header = ::subxt::sp_runtime::generic::Header<
-                        Self::BlockNumber, ::subxt::sp_runtime::traits::BlakeTwo256
-                    >;
-                    type Signature = ::subxt::sp_runtime::MultiSignature;
-                    type Extrinsic = ::subxt::sp_runtime::OpaqueExtrinsic;
-                }
+        let outer_items = quote! {
+            #outer_event
+            #outer_error
+            #outer_call
+        };
 
-                impl ::subxt::ExtrinsicExtraData<DefaultConfig> for DefaultConfig {
-                    type AccountData = AccountData;
-                    type Extra = ::subxt::DefaultExtra<DefaultConfig>;
-                }
+        let api_items = quote! {
+            /// Default configuration of common types for a target Substrate runtime.
+            #[derive(Clone, Debug, Default, Eq, PartialEq)]
+            pub struct DefaultConfig;
+
+            impl ::subxt::Config for DefaultConfig {
+                type Index = u32;
+                type BlockNumber = u32;
+                type Hash = ::subxt::sp_core::H256;
+                type Hashing = ::subxt::sp_runtime::traits::BlakeTwo256;
+                type AccountId = ::subxt::sp_runtime::AccountId32;
+                type Address = ::subxt::sp_runtime::MultiAddress<Self::AccountId, u32>;
+                type Header = ::subxt::sp_runtime::generic::Header<
+                    Self::BlockNumber, ::subxt::sp_runtime::traits::BlakeTwo256
+                >;
+                type Signature = ::subxt::sp_runtime::MultiSignature;
+                type Extrinsic = ::subxt::sp_runtime::OpaqueExtrinsic;
+            }
 
-                pub type AccountData = self::system::storage::Account;
+            impl ::subxt::ExtrinsicExtraData<DefaultConfig> for DefaultConfig {
+                type Extra = ::subxt::DefaultExtra<DefaultConfig>;
+            }
 
-                impl ::subxt::AccountData<DefaultConfig> for AccountData {
-                    fn nonce(result: &<Self as ::subxt::StorageEntry>::Value) -> <DefaultConfig as ::subxt::Config>::Index {
-                        result.nonce
-                    }
-                    fn storage_entry(account_id: <DefaultConfig as ::subxt::Config>::AccountId) -> Self {
-                        Self(account_id)
-                    }
+            pub struct RuntimeApi<T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>> {
+                pub client: ::subxt::Client<T>,
+            }
+
+            impl<T> ::core::convert::From<::subxt::Client<T>> for RuntimeApi<T>
+            where
+                T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
+            {
+                fn from(client: ::subxt::Client<T>) -> Self {
+                    Self { client }
                 }
+            }
 
-                pub struct RuntimeApi<T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>> {
-                    pub client: ::subxt::Client<T>,
+            impl<'a, T> RuntimeApi<T>
+            where
+                T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
+            {
+                pub fn storage(&'a self) -> StorageApi<'a, T> {
+                    StorageApi { client: &self.client }
                 }
 
-                impl<T> ::core::convert::From<::subxt::Client<T>> for RuntimeApi<T>
-                where
-                    T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
-                {
-                    fn from(client: ::subxt::Client<T>) -> Self {
-                        Self { client }
-                    }
+                pub fn tx(&'a self) -> TransactionApi<'a, T> {
+                    TransactionApi { client: &self.client }
                 }
 
-                impl<'a, T> RuntimeApi<T>
-                where
-                    T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
-                {
-                    pub fn storage(&'a self) -> StorageApi<'a, T> {
-                        StorageApi { client: &self.client }
-                    }
+                pub fn constants(&'a self) -> ConstantsApi<'a, T> {
+                    ConstantsApi { client: &self.client }
+                }
+            }
 
-                    pub fn tx(&'a self) -> TransactionApi<'a, T> {
-                        TransactionApi { client: &self.client }
+            pub struct StorageApi<'a, T>
+            where
+                T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
+            {
+                client: &'a ::subxt::Client<T>,
+            }
+
+            impl<'a, T> StorageApi<'a, T>
+            where
+                T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
+            {
+                #(
+                    pub fn #pallets_with_storage(&self) -> #pallets_with_storage::storage::StorageApi<'a, T> {
+                        #pallets_with_storage::storage::StorageApi::new(self.client)
                     }
-                }
+                )*
+            }
 
-                pub struct StorageApi<'a, T>
-                where
-                    T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
-                {
-                    client: &'a ::subxt::Client<T>,
-                }
+            pub struct TransactionApi<'a, T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>> {
+                client: &'a ::subxt::Client<T>,
+            }
 
-                impl<'a, T> StorageApi<'a, T>
-                where
-                    T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
-                {
-                    #(
-                        pub fn #pallets_with_storage(&self) -> #pallets_with_storage::storage::StorageApi<'a, T> {
-                            #pallets_with_storage::storage::StorageApi::new(self.client)
-                        }
-                    )*
-                }
+            impl<'a, T> TransactionApi<'a, T>
+            where
+                T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
+            {
+                #(
+                    pub fn #pallets_with_calls(&self) -> #pallets_with_calls::calls::TransactionApi<'a, T> {
+                        #pallets_with_calls::calls::TransactionApi::new(self.client)
+                    }
+                )*
+            }
 
-                pub struct TransactionApi<'a, T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>> {
-                    client: &'a ::subxt::Client<T>,
-                }
+            pub struct ConstantsApi<'a, T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>> {
+                client: &'a ::subxt::Client<T>,
+            }
 
-                impl<'a, T> TransactionApi<'a, T>
-                where
-                    T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
-                {
-                    #(
-                        pub fn #pallets_with_calls(&self) -> #pallets_with_calls::calls::TransactionApi<'a, T> {
-                            #pallets_with_calls::calls::TransactionApi::new(self.client)
-                        }
-                    )*
-                }
+            impl<'a, T> ConstantsApi<'a, T>
+            where
+                T: ::subxt::Config + ::subxt::ExtrinsicExtraData<T>,
+            {
+                #(
+                    pub fn #pallets_with_constants(&self) -> #pallets_with_constants::constants::ConstantsApi<'a, T> {
+                        #pallets_with_constants::constants::ConstantsApi::new(self.client)
+                    }
+                )*
             }
+        };
+
+        RuntimeParts {
+            mod_ident,
+            outer_items,
+            modules,
+            api_items,
         }
     }
 }
 
+/// The parts of the generated runtime API, which are either generated into a single module
+/// or split into files.
+struct RuntimeParts {
+    mod_ident: syn::Ident,
+    /// The outer enums of the runtime.
+    outer_items: TokenStream2,
+    /// The name and items of the module of each pallet, followed by the `runtime_types`.
+    modules: Vec<(syn::Ident, TokenStream2)>,
+    /// The `DefaultConfig` and the entry points of the API.
+    api_items: TokenStream2,
+}
+
 pub fn generate_structs_from_variants(
     type_gen: &TypeGenerator,
     type_id: u32,
@@ -73,12 +73,13 @@ fn generate_storage_entry_fns(
     storage_entry: &StorageEntryMetadata<PortableForm>,
 ) -> (TokenStream2, TokenStream2) {
     let entry_struct_ident = format_ident!("{}", storage_entry.name);
-    let (fields, entry_struct, constructor, key_impl) = match storage_entry.ty {
+    let (fields, entry_struct, constructor, key_impl, prefix_key) = match storage_entry.ty
+    {
         StorageEntryType::Plain(_) => {
             let entry_struct = quote!( pub struct #entry_struct_ident; );
             let constructor = quote!( #entry_struct_ident );
             let key_impl = quote!(::subxt::StorageEntryKey::Plain);
-            (vec![], entry_struct, constructor, key_impl)
+            (vec![], entry_struct, constructor, key_impl, None)
         }
         StorageEntryType::Map {
             ref key,
@@ -123,6 +124,14 @@ fn generate_storage_entry_fns(
                     };
                     let constructor =
                         quote!( #entry_struct_ident( #( #field_names ),* ) );
+                    // the first key of a multi-key map, used to iterate over all entries under it
+                    let prefix_key = if hashers.len() > 1 && hashers.len() == fields.len()
+                    {
+                        let hasher = &hashers[0];
+                        Some(quote!( ::subxt::StorageMapKey::new(&_0, #hasher) ))
+                    } else {
+                        None
+                    };
                     let keys = (0..tuple.fields().len()).into_iter().zip(hashers).map(
                         |(field, hasher)| {
                             let index = syn::Index::from(field);
@@ -134,7 +143,7 @@ fn generate_storage_entry_fns(
                             vec![ #( #keys ),* ]
                         )
                     };
-                    (fields, entry_struct, constructor, key_impl)
+                    (fields, entry_struct, constructor, key_impl, prefix_key)
                 }
                 _ => {
                     let ty_path = type_gen.resolve_type_path(key.id(), &[]);
@@ -151,7 +160,7 @@ fn generate_storage_entry_fns(
                             vec![ ::subxt::StorageMapKey::new(&self.0, #hasher) ]
                         )
                     };
-                    (fields, entry_struct, constructor, key_impl)
+                    (fields, entry_struct, constructor, key_impl, None)
                 }
             }
         }
@@ -190,6 +199,22 @@ fn generate_storage_entry_fns(
         }
     };
 
+    let client_iter_prefix_fn = if let Some(prefix_key) = prefix_key {
+        let fn_name_iter_prefix = format_ident!("{}_iter_prefix", fn_name);
+        let (first_key_name, first_key_ty) = &fields[0];
+        quote! (
+            pub async fn #fn_name_iter_prefix(
+                &self,
+                #first_key_name: #first_key_ty,
+                hash: ::core::option::Option<T::Hash>,
+            ) -> ::core::result::Result<::subxt::KeyIter<'a, T, #entry_struct_ident>, ::subxt::Error> {
+                self.client.storage().iter_prefix(vec![ #prefix_key ], hash).await
+            }
+        )
+    } else {
+        quote!()
+    };
+
     let client_iter_fn = if matches!(storage_entry.ty, StorageEntryType::Map { .. }) {
         quote! (
             pub async fn #fn_name_iter(
@@ -203,9 +228,14 @@ fn generate_storage_entry_fns(
         quote!()
     };
 
+    let fn_name_key = format_ident!("{}_key", fn_name);
+    let fn_name_subscribe = format_ident!("{}_subscribe", fn_name);
+    let fn_name_at_number = format_ident!("{}_at_number", fn_name);
+    let key_names = fields.iter().map(|(field_name, _)| field_name);
     let key_args = fields
         .iter()
-        .map(|(field_name, field_type)| quote!( #field_name: #field_type ));
+        .map(|(field_name, field_type)| quote!( #field_name: #field_type ))
+        .collect::<Vec<_>>();
     let client_fns = quote! {
         pub async fn #fn_name(
             &self,
@@ -216,7 +246,34 @@ fn generate_storage_entry_fns(
             self.client.storage().#fetch(&entry, hash).await
         }
 
+        pub async fn #fn_name_at_number(
+            &self,
+            #( #key_args, )*
+            number: T::BlockNumber,
+        ) -> ::core::result::Result<#return_ty, ::subxt::Error> {
+            let hash = self.client.storage().block_hash(number).await?;
+            self.#fn_name( #( #key_names, )* Some(hash) ).await
+        }
+
+        pub async fn #fn_name_subscribe(
+            &self,
+            #( #key_args, )*
+        ) -> ::core::result::Result<::subxt::storage::StorageEntrySubscription<T, #entry_struct_ident>, ::subxt::Error> {
+            let entry = #constructor;
+            self.client.storage().subscribe_entry(&entry).await
+        }
+
+        pub fn #fn_name_key(
+            &self,
+            #( #key_args, )*
+        ) -> ::subxt::sp_core::storage::StorageKey {
+            let entry = #constructor;
+            let prefix = ::subxt::storage::StorageKeyPrefix::new::<#entry_struct_ident>();
+            ::subxt::StorageEntry::key(&entry).final_key(prefix)
+        }
+
         #client_iter_fn
+        #client_iter_prefix_fn
     };
 
     (storage_entry_type, client_fns)
@@ -0,0 +1,208 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+fn node_runtime_metadata() -> RuntimeMetadataPrefixed {
+    let bytes = include_bytes!("../../../tests/integration/node_runtime.scale");
+    Decode::decode(&mut &bytes[..]).unwrap()
+}
+
+#[test]
+fn generate_pallets_excludes_other_pallets() {
+    let mut generator = RuntimeGenerator::new(node_runtime_metadata());
+    generator.generate_pallets(vec!["System".to_string(), "Balances".to_string()]);
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+
+    assert!(runtime_api.contains("pub mod system {"));
+    assert!(runtime_api.contains("pub mod balances {"));
+    assert!(!runtime_api.contains("pub mod staking {"));
+
+    // types referenced by the generated pallets are still generated
+    assert!(runtime_api.contains("pub mod pallet_balances {"));
+    assert!(runtime_api.contains("pub mod sp_runtime {"));
+    assert!(!runtime_api.contains("pub mod pallet_staking {"));
+}
+
+#[test]
+fn generate_all_pallets_by_default() {
+    let generator = RuntimeGenerator::new(node_runtime_metadata());
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+
+    assert!(runtime_api.contains("pub mod staking {"));
+    assert!(runtime_api.contains("pub mod pallet_staking {"));
+}
+
+#[test]
+fn generate_outer_error_with_pallet_errors() {
+    let generator = RuntimeGenerator::new(node_runtime_metadata());
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+
+    assert!(runtime_api.contains("pub enum Error {"));
+    assert!(runtime_api.contains("Balances (balances :: Error)"));
+}
+
+#[test]
+fn generate_call_hashes() {
+    let generator = RuntimeGenerator::new(node_runtime_metadata());
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+
+    assert!(runtime_api.contains(
+        "const FUNCTION : & 'static str = \"transfer\" ; const CALL_HASH : [u8 ; 32] ="
+    ));
+}
+
+#[test]
+fn generate_call_constructors() {
+    let generator = RuntimeGenerator::new(node_runtime_metadata());
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+
+    assert!(runtime_api.contains("pub fn transfer_call"));
+    // the addresses convert into the address type of the metadata, like into the one of
+    // the config for the transaction api
+    assert!(runtime_api.contains(
+        "dest : impl :: core :: convert :: Into < :: subxt :: sp_runtime :: MultiAddress < :: subxt :: sp_core :: crypto :: AccountId32 , () > > ,"
+    ));
+    assert!(runtime_api.contains(
+        "-> super :: Call { super :: Call :: transfer { dest : dest . into () , value , } }"
+    ));
+}
+
+#[test]
+fn generate_pallets_omits_outer_call_referencing_other_pallets() {
+    let mut generator = RuntimeGenerator::new(node_runtime_metadata());
+    generator.generate_pallets(vec!["System".to_string(), "Balances".to_string()]);
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+
+    assert!(
+        !runtime_api.contains("pub type Call = runtime_types :: node_runtime :: Call ;")
+    );
+}
+
+#[test]
+fn generate_pallets_with_outer_call_referenced_by_pallet() {
+    let mut generator = RuntimeGenerator::new(node_runtime_metadata());
+    generator.generate_pallets(vec!["System".to_string(), "Utility".to_string()]);
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+
+    // `Utility::batch` takes the outer call enum
+    assert!(
+        runtime_api.contains("pub type Call = runtime_types :: node_runtime :: Call ;")
+    );
+    assert!(runtime_api.contains("pub mod pallet_staking {"));
+}
+
+#[test]
+fn generate_calls_generic_over_address() {
+    let generator = RuntimeGenerator::new(node_runtime_metadata());
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+
+    // the call structs default to the address type of the metadata
+    assert!(runtime_api.contains(
+        "pub struct Transfer < Address = :: subxt :: sp_runtime :: MultiAddress < :: subxt :: sp_core :: crypto :: AccountId32 , () > >"
+    ));
+    assert!(runtime_api.contains(
+        "impl < Address : :: subxt :: codec :: Encode > :: subxt :: Call for Transfer < Address >"
+    ));
+    // and the transaction api takes anything converting into the address type of the config
+    assert!(
+        runtime_api.contains("dest : impl :: core :: convert :: Into < T :: Address > ,")
+    );
+    assert!(runtime_api
+        .contains(":: subxt :: SubmittableExtrinsic < T , Transfer < T :: Address > >"));
+}
+
+#[test]
+fn generate_runtime_files_with_a_file_per_pallet() {
+    let generator = RuntimeGenerator::new(node_runtime_metadata());
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let files = generator.generate_runtime_files(item_mod, Default::default());
+    let names = files
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names[0], "mod.rs");
+    assert!(names.contains(&"balances.rs"));
+    assert!(names.contains(&"staking.rs"));
+    assert!(names.contains(&"runtime_types.rs"));
+
+    // the root module declares the modules of the other files
+    let root = files[0].1.to_string();
+    assert!(root.contains("pub mod balances ;"));
+    assert!(root.contains("pub mod runtime_types ;"));
+    assert!(root.contains("pub struct DefaultConfig ;"));
+    assert!(!root.contains("pub mod calls {"));
+
+    // and each file holds the items of the module generated inline by `generate_runtime`
+    let item_mod = parse_quote!(
+        pub mod api {}
+    );
+    let runtime_api = generator
+        .generate_runtime(item_mod, Default::default())
+        .to_string();
+    let balances = files
+        .iter()
+        .find(|(name, _)| name == "balances.rs")
+        .unwrap()
+        .1
+        .to_string();
+    assert!(balances.starts_with("use super :: runtime_types ;"));
+    assert!(!balances.contains("pub mod pallet_staking {"));
+    assert!(runtime_api.contains(&format!("pub mod balances {{ {} }}", balances)));
+}
@@ -14,16 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
 use syn::punctuated::Punctuated;
 
 #[derive(Debug, Clone)]
 pub struct GeneratedTypeDerives {
     derives: Punctuated<syn::Path, syn::Token![,]>,
+    /// Additional derives for specific types, keyed by the path of the type in the metadata.
+    type_derives: HashMap<String, Punctuated<syn::Path, syn::Token![,]>>,
 }
 
 impl GeneratedTypeDerives {
     pub fn new(derives: Punctuated<syn::Path, syn::Token!(,)>) -> Self {
-        Self { derives }
+        Self {
+            derives,
+            type_derives: HashMap::new(),
+        }
     }
 
     pub fn append(&mut self, derives: impl Iterator<Item = syn::Path>) {
@@ -31,6 +37,45 @@ impl GeneratedTypeDerives {
             self.derives.push(derive)
         }
     }
+
+    /// Add derives for the type with the given path in the metadata, e.g.
+    /// `sp_runtime::DispatchError`.
+    ///
+    /// The derives are also added to the generated types nested within that type.
+    pub fn append_for_type(
+        &mut self,
+        type_path: String,
+        derives: impl Iterator<Item = syn::Path>,
+    ) {
+        let type_derives = self.type_derives.entry(type_path).or_default();
+        for derive in derives {
+            if !type_derives.iter().any(|d| d == &derive) {
+                type_derives.push(derive)
+            }
+        }
+    }
+
+    /// Returns the additional derives for specific types, keyed by type path.
+    pub fn type_derives(
+        &self,
+    ) -> &HashMap<String, Punctuated<syn::Path, syn::Token![,]>> {
+        &self.type_derives
+    }
+
+    /// Returns the derives for the type with the given path, i.e. the derives for all types
+    /// along with the additional derives for that type.
+    pub fn for_type(&self, type_path: &str) -> Self {
+        let mut derives = Self::new(self.derives.clone());
+        if let Some(type_derives) = self.type_derives.get(type_path) {
+            derives.append(
+                type_derives
+                    .iter()
+                    .filter(|d| !self.derives.iter().any(|derive| derive == *d))
+                    .cloned(),
+            )
+        }
+        derives
+    }
 }
 
 impl Default for GeneratedTypeDerives {
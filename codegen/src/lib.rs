@@ -25,6 +25,7 @@ mod types;
 pub use self::{
     api::{
         generate_runtime_api,
+        generate_runtime_api_from_url,
         RuntimeGenerator,
     },
     derives::GeneratedTypeDerives,
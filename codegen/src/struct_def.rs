@@ -17,6 +17,7 @@
 use super::GeneratedTypeDerives;
 use crate::types::{
     TypeGenerator,
+    TypeParameter,
     TypePath,
 };
 use heck::CamelCase as _;
@@ -34,6 +35,8 @@ pub struct StructDef {
     pub fields: StructDefFields,
     pub field_visibility: Option<syn::Visibility>,
     pub derives: GeneratedTypeDerives,
+    /// The type parameters of the struct, along with their default types.
+    pub type_params: Vec<(TypeParameter, TypePath)>,
 }
 
 #[derive(Debug)]
@@ -92,6 +95,28 @@ impl StructDef {
             fields,
             field_visibility,
             derives,
+            type_params: Vec::new(),
+        }
+    }
+
+    /// Makes the type of the named field the type parameter `param`, which defaults to the
+    /// type of the field.
+    ///
+    /// Fields of the same type can share a type parameter.
+    pub fn make_field_generic(&mut self, field: &syn::Ident, param: TypeParameter) {
+        let ty = match self.fields {
+            StructDefFields::Named(ref mut fields) => {
+                fields
+                    .iter_mut()
+                    .find(|(name, _)| name == field)
+                    .map(|(_, ty)| ty)
+            }
+            StructDefFields::Unnamed(_) => None,
+        }
+        .unwrap_or_else(|| abort_call_site!("Struct has no field named {}", field));
+        let default = std::mem::replace(ty, TypePath::Parameter(param.clone()));
+        if !self.type_params.iter().any(|(p, _)| p == &param) {
+            self.type_params.push((param, default));
         }
     }
 
@@ -108,6 +133,13 @@ impl quote::ToTokens for StructDef {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let visibility = &self.field_visibility;
         let derives = &self.derives;
+        let type_params = (!self.type_params.is_empty()).then(|| {
+            let type_params = self
+                .type_params
+                .iter()
+                .map(|(param, default)| quote!( #param = #default ));
+            quote!( < #( #type_params ),* > )
+        });
         tokens.extend(match self.fields {
             StructDefFields::Named(ref named_fields) => {
                 let fields = named_fields.iter().map(|(name, ty)| {
@@ -118,7 +150,7 @@ impl quote::ToTokens for StructDef {
                 let name = &self.name;
                 quote! {
                     #derives
-                    pub struct #name {
+                    pub struct #name #type_params {
                         #( #fields ),*
                     }
                 }
@@ -132,7 +164,7 @@ impl quote::ToTokens for StructDef {
                 let name = &self.name;
                 quote! {
                     #derives
-                    pub struct #name (
+                    pub struct #name #type_params (
                         #( #fields ),*
                     );
                 }
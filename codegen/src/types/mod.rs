@@ -38,6 +38,7 @@ use scale_info::{
 use std::collections::{
     BTreeMap,
     HashMap,
+    HashSet,
 };
 
 pub use self::{
@@ -72,6 +73,8 @@ impl<'a> TypeGenerator<'a> {
         derives: GeneratedTypeDerives,
     ) -> Self {
         let root_mod_ident = Ident::new(root_mod, Span::call_site());
+        let derives =
+            Self::propagate_type_derives(type_registry, &type_substitutes, derives);
         Self {
             types_mod_ident: root_mod_ident,
             type_registry,
@@ -82,10 +85,57 @@ impl<'a> TypeGenerator<'a> {
 
     /// Generate a module containing all types defined in the supplied type registry.
     pub fn generate_types_mod(&'a self) -> Module<'a> {
+        self.generate_types_mod_filtered(|_| true)
+    }
+
+    /// Generate a module containing only the types with the given ids, and the types they
+    /// reference, defined in the supplied type registry.
+    pub fn generate_types_mod_for(
+        &'a self,
+        root_type_ids: impl IntoIterator<Item = u32>,
+    ) -> Module<'a> {
+        let referenced = self.referenced_type_ids(root_type_ids);
+        self.generate_types_mod_filtered(|id| referenced.contains(&id))
+    }
+
+    /// Returns the ids of the given types and of all the types they reference.
+    pub fn referenced_type_ids(
+        &self,
+        root_type_ids: impl IntoIterator<Item = u32>,
+    ) -> HashSet<u32> {
+        let mut referenced = HashSet::new();
+        let mut pending = root_type_ids.into_iter().collect::<Vec<_>>();
+        while let Some(id) = pending.pop() {
+            if !referenced.insert(id) {
+                continue
+            }
+            let ty = self.resolve_type(id);
+            if self
+                .type_substitutes
+                .contains_key(&ty.path().segments().join("::"))
+            {
+                // only the type parameters of substitutes are generated
+                pending.extend(
+                    ty.type_params()
+                        .iter()
+                        .filter_map(|tp| tp.ty())
+                        .map(|tp| tp.id()),
+                );
+            } else {
+                pending.extend(nested_type_ids(&ty));
+            }
+        }
+        referenced
+    }
+
+    fn generate_types_mod_filtered(&'a self, filter: impl Fn(u32) -> bool) -> Module<'a> {
         let mut root_mod =
             Module::new(self.types_mod_ident.clone(), self.types_mod_ident.clone());
 
         for (id, ty) in self.type_registry.types().iter().enumerate() {
+            if !filter(id as u32) {
+                continue
+            }
             if ty.ty().path().namespace().is_empty() {
                 // prelude types e.g. Option/Result have no namespace, so we don't generate them
                 continue
@@ -132,6 +182,11 @@ impl<'a> TypeGenerator<'a> {
         }
     }
 
+    /// Returns the type registry the types are generated from.
+    pub fn type_registry(&self) -> &'a PortableRegistry {
+        self.type_registry
+    }
+
     /// # Panics
     ///
     /// If no type with the given id found in the type registry.
@@ -208,6 +263,90 @@ impl<'a> TypeGenerator<'a> {
     pub fn derives(&self) -> &GeneratedTypeDerives {
         &self.derives
     }
+
+    /// Returns the derives with which the given generated type will be decorated, i.e. the
+    /// derives for all types along with any derives specified for that type.
+    pub fn type_derives(&self, ty: &Type<PortableForm>) -> GeneratedTypeDerives {
+        self.derives.for_type(&ty.path().segments().join("::"))
+    }
+
+    /// Adds the derives specified for a type to all the generated types nested within it,
+    /// since e.g. deriving `serde::Serialize` for a type requires it for its fields.
+    fn propagate_type_derives(
+        type_registry: &PortableRegistry,
+        type_substitutes: &HashMap<String, syn::TypePath>,
+        mut derives: GeneratedTypeDerives,
+    ) -> GeneratedTypeDerives {
+        let roots = type_registry
+            .types()
+            .iter()
+            .enumerate()
+            .filter_map(|(id, ty)| {
+                let path = ty.ty().path().segments().join("::");
+                derives
+                    .type_derives()
+                    .get(&path)
+                    .map(|type_derives| (id as u32, type_derives.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        for (root, type_derives) in roots {
+            let mut visited = HashSet::new();
+            let mut pending = vec![root];
+            while let Some(id) = pending.pop() {
+                if !visited.insert(id) {
+                    continue
+                }
+                let ty = type_registry
+                    .resolve(id)
+                    .unwrap_or_else(|| panic!("No type with id {} found", id));
+                let path = ty.path().segments().join("::");
+                if type_substitutes.contains_key(&path) {
+                    // substitutes are not generated, so can't be given derives
+                    continue
+                }
+                if !ty.path().namespace().is_empty() {
+                    derives.append_for_type(path, type_derives.iter().cloned());
+                }
+
+                pending.extend(nested_type_ids(ty));
+            }
+        }
+        derives
+    }
+}
+
+/// Returns the ids of the types referenced by the given type, i.e. its type parameters and
+/// the types of its fields.
+fn nested_type_ids(ty: &Type<PortableForm>) -> Vec<u32> {
+    let mut ids = ty
+        .type_params()
+        .iter()
+        .filter_map(|tp| tp.ty())
+        .map(|tp| tp.id())
+        .collect::<Vec<_>>();
+    match ty.type_def() {
+        TypeDef::Composite(composite) => {
+            ids.extend(composite.fields().iter().map(|f| f.ty().id()))
+        }
+        TypeDef::Variant(variant) => {
+            ids.extend(
+                variant
+                    .variants()
+                    .iter()
+                    .flat_map(|v| v.fields().iter().map(|f| f.ty().id())),
+            )
+        }
+        TypeDef::Sequence(seq) => ids.push(seq.type_param().id()),
+        TypeDef::Array(arr) => ids.push(arr.type_param().id()),
+        TypeDef::Tuple(tuple) => ids.extend(tuple.fields().iter().map(|f| f.id())),
+        TypeDef::Compact(compact) => ids.push(compact.type_param().id()),
+        TypeDef::BitSequence(seq) => {
+            ids.extend([seq.bit_order_type().id(), seq.bit_store_type().id()])
+        }
+        TypeDef::Primitive(_) => (),
+    }
+    ids
 }
 
 #[derive(Debug)]
@@ -221,16 +360,11 @@ pub struct Module<'a> {
 impl<'a> ToTokens for Module<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = &self.name;
-        let root_mod = &self.root_mod;
-        let modules = self.children.values();
-        let types = self.types.values().clone();
+        let items = self.items();
 
         tokens.extend(quote! {
             pub mod #name {
-                use super::#root_mod;
-
-                #( #modules )*
-                #( #types )*
+                #items
             }
         })
     }
@@ -250,4 +384,19 @@ impl<'a> Module<'a> {
     pub fn ident(&self) -> &Ident {
         &self.name
     }
+
+    /// Returns the items of the module, without the enclosing `mod` item, e.g. to write them
+    /// to a file of their own.
+    pub fn items(&self) -> TokenStream {
+        let root_mod = &self.root_mod;
+        let modules = self.children.values();
+        let types = self.types.values();
+
+        quote! {
+            use super::#root_mod;
+
+            #( #modules )*
+            #( #types )*
+        }
+    }
 }
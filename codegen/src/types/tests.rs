@@ -126,6 +126,171 @@ fn generate_struct_with_a_struct_field() {
     )
 }
 
+#[test]
+fn substitute_struct_field_type() {
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    struct Parent {
+        a: bool,
+        b: Child,
+    }
+
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    struct Child {
+        a: i32,
+    }
+
+    let mut registry = Registry::new();
+    registry.register_type(&meta_type::<Parent>());
+    let portable_types: PortableRegistry = registry.into();
+
+    let type_substitutes = [(
+        "subxt_codegen::types::tests::Child".to_string(),
+        syn::parse_quote!(::my_crate::Child),
+    )]
+    .iter()
+    .cloned()
+    .collect();
+    let type_gen = TypeGenerator::new(
+        &portable_types,
+        "root",
+        type_substitutes,
+        Default::default(),
+    );
+    let types = type_gen.generate_types_mod();
+    let tests_mod = get_mod(&types, MOD_PATH).unwrap();
+
+    assert_eq!(
+        tests_mod.into_token_stream().to_string(),
+        quote! {
+            pub mod tests {
+                use super::root;
+
+                #[derive(::subxt::codec::Encode, ::subxt::codec::Decode)]
+                pub struct Parent {
+                    pub a: ::core::primitive::bool,
+                    pub b: ::my_crate::Child,
+                }
+            }
+        }
+        .to_string()
+    )
+}
+
+#[test]
+fn substitute_generic_type_keeps_type_params() {
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    struct Parent {
+        a: Wrapper<u8>,
+    }
+
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    struct Wrapper<T> {
+        a: T,
+    }
+
+    let mut registry = Registry::new();
+    registry.register_type(&meta_type::<Parent>());
+    let portable_types: PortableRegistry = registry.into();
+
+    let type_substitutes = [(
+        "subxt_codegen::types::tests::Wrapper".to_string(),
+        syn::parse_quote!(::my_crate::Wrapper),
+    )]
+    .iter()
+    .cloned()
+    .collect();
+    let type_gen = TypeGenerator::new(
+        &portable_types,
+        "root",
+        type_substitutes,
+        Default::default(),
+    );
+    let types = type_gen.generate_types_mod();
+    let tests_mod = get_mod(&types, MOD_PATH).unwrap();
+
+    assert_eq!(
+        tests_mod.into_token_stream().to_string(),
+        quote! {
+            pub mod tests {
+                use super::root;
+
+                #[derive(::subxt::codec::Encode, ::subxt::codec::Decode)]
+                pub struct Parent {
+                    pub a: ::my_crate::Wrapper<::core::primitive::u8>,
+                }
+            }
+        }
+        .to_string()
+    )
+}
+
+#[test]
+fn derives_for_type_are_added_to_nested_types() {
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    struct Parent {
+        a: bool,
+        b: Child,
+    }
+
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    struct Child {
+        a: i32,
+    }
+
+    #[allow(unused)]
+    #[derive(TypeInfo)]
+    struct Other {
+        a: u8,
+    }
+
+    let mut registry = Registry::new();
+    registry.register_type(&meta_type::<Parent>());
+    registry.register_type(&meta_type::<Other>());
+    let portable_types: PortableRegistry = registry.into();
+
+    let mut derives = GeneratedTypeDerives::default();
+    derives.append_for_type(
+        "subxt_codegen::types::tests::Parent".to_string(),
+        [syn::parse_quote!(::serde::Serialize)].iter().cloned(),
+    );
+    let type_gen =
+        TypeGenerator::new(&portable_types, "root", Default::default(), derives);
+    let types = type_gen.generate_types_mod();
+    let tests_mod = get_mod(&types, MOD_PATH).unwrap();
+
+    assert_eq!(
+        tests_mod.into_token_stream().to_string(),
+        quote! {
+            pub mod tests {
+                use super::root;
+
+                #[derive(::subxt::codec::Encode, ::subxt::codec::Decode, ::serde::Serialize)]
+                pub struct Child {
+                    pub a: ::core::primitive::i32,
+                }
+
+                #[derive(::subxt::codec::Encode, ::subxt::codec::Decode)]
+                pub struct Other {
+                    pub a: ::core::primitive::u8,
+                }
+
+                #[derive(::subxt::codec::Encode, ::subxt::codec::Decode, ::serde::Serialize)]
+                pub struct Parent {
+                    pub a: ::core::primitive::bool,
+                    pub b: root::subxt_codegen::types::tests::Child,
+                }
+            }
+        }
+        .to_string()
+    )
+}
+
 #[test]
 fn generate_tuple_struct() {
     #[allow(unused)]
@@ -79,7 +79,7 @@ impl<'a> quote::ToTokens for TypeDefGen<'a> {
             syn::Type::Path(path)
         });
 
-        let derives = self.type_gen.derives();
+        let derives = self.type_gen.type_derives(&self.ty);
 
         match self.ty.type_def() {
             TypeDef::Composite(composite) => {
@@ -208,6 +208,17 @@ pub struct TypeParameter {
     pub(super) name: proc_macro2::Ident,
 }
 
+impl TypeParameter {
+    /// Creates a type parameter with the given name, standing in for the type with the
+    /// given id.
+    pub(crate) fn new(concrete_type_id: u32, name: proc_macro2::Ident) -> Self {
+        Self {
+            concrete_type_id,
+            name,
+        }
+    }
+}
+
 impl quote::ToTokens for TypeParameter {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.name.to_tokens(tokens)
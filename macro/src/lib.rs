@@ -18,7 +18,10 @@ extern crate proc_macro;
 
 use darling::FromMeta;
 use proc_macro::TokenStream;
-use proc_macro_error::proc_macro_error;
+use proc_macro_error::{
+    abort_call_site,
+    proc_macro_error,
+};
 use syn::{
     parse_macro_input,
     punctuated::Punctuated,
@@ -26,14 +29,34 @@ use syn::{
 
 #[derive(Debug, FromMeta)]
 struct RuntimeMetadataArgs {
-    runtime_metadata_path: String,
+    #[darling(default)]
+    runtime_metadata_path: Option<String>,
+    #[darling(default)]
+    runtime_metadata_url: Option<String>,
     #[darling(default)]
     generated_type_derives: Option<GeneratedTypeDerives>,
+    #[darling(multiple)]
+    derive_for_type: Vec<DeriveForType>,
+    #[darling(default)]
+    generate_pallets: Option<GeneratePallets>,
 }
 
 #[derive(Debug, FromMeta)]
 struct GeneratedTypeDerives(Punctuated<syn::Path, syn::Token![,]>);
 
+/// The names of the pallets to generate, e.g. `generate_pallets = "System, Balances"`.
+#[derive(Debug, FromMeta)]
+struct GeneratePallets(Punctuated<syn::Ident, syn::Token![,]>);
+
+/// Additional derives for a generated type, e.g.
+/// `derive_for_type(type = "sp_runtime::DispatchError", derive = "serde::Serialize")`.
+#[derive(Debug, FromMeta)]
+struct DeriveForType {
+    #[darling(rename = "type")]
+    ty: String,
+    derive: GeneratedTypeDerives,
+}
+
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn subxt(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -45,11 +68,37 @@ pub fn subxt(args: TokenStream, input: TokenStream) -> TokenStream {
         Err(e) => return TokenStream::from(e.write_errors()),
     };
 
-    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
-    let root_path = std::path::Path::new(&root);
-    let path = root_path.join(args.runtime_metadata_path);
+    let mut derives = subxt_codegen::GeneratedTypeDerives::default();
+    if let Some(user_derives) = args.generated_type_derives {
+        derives.append(user_derives.0.iter().cloned())
+    }
+    for derive_for_type in args.derive_for_type {
+        derives.append_for_type(derive_for_type.ty, derive_for_type.derive.0.into_iter())
+    }
 
-    let generated_type_derives = args.generated_type_derives.map(|derives| derives.0);
+    let pallets = args.generate_pallets.map(|pallets| {
+        pallets
+            .0
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    });
 
-    subxt_codegen::generate_runtime_api(item_mod, &path, generated_type_derives).into()
+    match (args.runtime_metadata_path, args.runtime_metadata_url) {
+        (Some(path), None) => {
+            let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
+            let root_path = std::path::Path::new(&root);
+            let path = root_path.join(path);
+            subxt_codegen::generate_runtime_api(item_mod, &path, derives, pallets).into()
+        }
+        (None, Some(url)) => {
+            subxt_codegen::generate_runtime_api_from_url(item_mod, &url, derives, pallets)
+                .into()
+        }
+        _ => {
+            abort_call_site!(
+                "Specify one of `runtime_metadata_path` or `runtime_metadata_url`"
+            )
+        }
+    }
 }
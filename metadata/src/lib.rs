@@ -0,0 +1,250 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Hashing of the types in FRAME metadata.
+//!
+//! The hash of a call is computed both from the metadata the code is generated from, and from
+//! the metadata of the node at runtime, to detect when the generated code is out of date.
+
+use scale_info::{
+    form::PortableForm,
+    Field,
+    PortableRegistry,
+    TypeDef,
+    Variant,
+};
+use std::collections::HashSet;
+
+/// Hashed in place of a type which is already being hashed, to terminate recursive types.
+const MAGIC_RECURSIVE_TYPE_VALUE: &[u8] = &[123];
+
+fn hash(bytes: &[u8]) -> [u8; 32] {
+    sp_core_hashing::twox_256(bytes)
+}
+
+/// Hash the concatenation of two hashes, so that the order of the hashes matters.
+fn hash_hashes(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&a);
+    bytes[32..].copy_from_slice(&b);
+    hash(&bytes)
+}
+
+fn get_field_hash(
+    registry: &PortableRegistry,
+    field: &Field<PortableForm>,
+    visited_ids: &mut HashSet<u32>,
+) -> [u8; 32] {
+    let field_name_hash =
+        hash(field.name().map(|name| name.as_bytes()).unwrap_or_default());
+    let type_hash = get_type_hash_recurse(registry, field.ty().id(), visited_ids);
+    hash_hashes(field_name_hash, type_hash)
+}
+
+fn get_fields_hash(
+    registry: &PortableRegistry,
+    fields: &[Field<PortableForm>],
+    visited_ids: &mut HashSet<u32>,
+) -> [u8; 32] {
+    fields.iter().fold(hash(&[]), |acc, field| {
+        hash_hashes(acc, get_field_hash(registry, field, visited_ids))
+    })
+}
+
+fn get_variant_hash(
+    registry: &PortableRegistry,
+    variant: &Variant<PortableForm>,
+    visited_ids: &mut HashSet<u32>,
+) -> [u8; 32] {
+    let mut bytes = vec![variant.index()];
+    bytes.extend(variant.name().as_bytes());
+    hash_hashes(
+        hash(&bytes),
+        get_fields_hash(registry, variant.fields(), visited_ids),
+    )
+}
+
+fn get_type_def_hash(
+    registry: &PortableRegistry,
+    type_def: &TypeDef<PortableForm>,
+    visited_ids: &mut HashSet<u32>,
+) -> [u8; 32] {
+    // each kind of type def is tagged, so that e.g. a tuple and a struct with unnamed
+    // fields of the same types do not hash the same
+    match type_def {
+        TypeDef::Composite(composite) => {
+            hash_hashes(
+                hash(&[0]),
+                get_fields_hash(registry, composite.fields(), visited_ids),
+            )
+        }
+        TypeDef::Variant(variant) => {
+            variant.variants().iter().fold(hash(&[1]), |acc, variant| {
+                hash_hashes(acc, get_variant_hash(registry, variant, visited_ids))
+            })
+        }
+        TypeDef::Sequence(sequence) => {
+            hash_hashes(
+                hash(&[2]),
+                get_type_hash_recurse(registry, sequence.type_param().id(), visited_ids),
+            )
+        }
+        TypeDef::Array(array) => {
+            let mut bytes = vec![3];
+            bytes.extend(array.len().to_le_bytes());
+            hash_hashes(
+                hash(&bytes),
+                get_type_hash_recurse(registry, array.type_param().id(), visited_ids),
+            )
+        }
+        TypeDef::Tuple(tuple) => {
+            tuple.fields().iter().fold(hash(&[4]), |acc, ty| {
+                hash_hashes(acc, get_type_hash_recurse(registry, ty.id(), visited_ids))
+            })
+        }
+        TypeDef::Primitive(primitive) => {
+            let mut bytes = vec![5];
+            bytes.extend(format!("{:?}", primitive).as_bytes());
+            hash(&bytes)
+        }
+        TypeDef::Compact(compact) => {
+            hash_hashes(
+                hash(&[6]),
+                get_type_hash_recurse(registry, compact.type_param().id(), visited_ids),
+            )
+        }
+        TypeDef::BitSequence(bit_sequence) => {
+            let bit_order_hash = get_type_hash_recurse(
+                registry,
+                bit_sequence.bit_order_type().id(),
+                visited_ids,
+            );
+            let bit_store_hash = get_type_hash_recurse(
+                registry,
+                bit_sequence.bit_store_type().id(),
+                visited_ids,
+            );
+            hash_hashes(hash(&[7]), hash_hashes(bit_order_hash, bit_store_hash))
+        }
+    }
+}
+
+fn get_type_hash_recurse(
+    registry: &PortableRegistry,
+    id: u32,
+    visited_ids: &mut HashSet<u32>,
+) -> [u8; 32] {
+    if !visited_ids.insert(id) {
+        return hash(MAGIC_RECURSIVE_TYPE_VALUE)
+    }
+    let type_hash = match registry.resolve(id) {
+        Some(ty) => get_type_def_hash(registry, ty.type_def(), visited_ids),
+        None => hash(&[]),
+    };
+    visited_ids.remove(&id);
+    type_hash
+}
+
+/// Obtain the hash of the shape of the type with the given id in the registry.
+///
+/// The hash covers the names and types of the fields and variants of the type, but not the
+/// path of the type, so that types which are renamed or moved hash the same.
+pub fn get_type_hash(registry: &PortableRegistry, id: u32) -> [u8; 32] {
+    get_type_hash_recurse(registry, id, &mut HashSet::new())
+}
+
+/// Obtain the hash of the arguments of a call, given the variant of the call in the call type
+/// of its pallet.
+pub fn get_call_hash(
+    registry: &PortableRegistry,
+    variant: &Variant<PortableForm>,
+) -> [u8; 32] {
+    get_fields_hash(registry, variant.fields(), &mut HashSet::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale_info::{
+        meta_type,
+        Registry,
+        TypeInfo,
+    };
+
+    fn type_hash<T: TypeInfo + 'static>() -> [u8; 32] {
+        let mut registry = Registry::new();
+        let id = registry.register_type(&meta_type::<T>()).id();
+        let registry: PortableRegistry = registry.into();
+        get_type_hash(&registry, id)
+    }
+
+    #[test]
+    fn hash_ignores_path_of_types() {
+        mod a {
+            #[allow(unused)]
+            #[derive(scale_info::TypeInfo)]
+            pub struct S {
+                pub a: bool,
+                pub b: u32,
+            }
+        }
+        mod b {
+            #[allow(unused)]
+            #[derive(scale_info::TypeInfo)]
+            pub struct S {
+                pub a: bool,
+                pub b: u32,
+            }
+        }
+        assert_eq!(type_hash::<a::S>(), type_hash::<b::S>());
+    }
+
+    #[test]
+    fn hash_differs_for_different_fields() {
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        struct A {
+            a: bool,
+            b: u32,
+        }
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        struct B {
+            a: bool,
+            b: u64,
+        }
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        struct C {
+            a: bool,
+            c: u32,
+        }
+        assert_ne!(type_hash::<A>(), type_hash::<B>());
+        assert_ne!(type_hash::<A>(), type_hash::<C>());
+        assert_ne!(type_hash::<(bool, u32)>(), type_hash::<A>());
+    }
+
+    #[test]
+    fn hash_of_recursive_type_terminates() {
+        #[allow(unused)]
+        #[derive(TypeInfo)]
+        enum Tree {
+            Leaf(u32),
+            Node(Box<Tree>, Box<Tree>),
+        }
+        assert_eq!(type_hash::<Tree>(), type_hash::<Tree>());
+    }
+}
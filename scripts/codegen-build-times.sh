@@ -0,0 +1,68 @@
+#!/usr/bin/env bash
+# Compares the build times of the API generated for the `node_runtime` fixture, generated
+# into a single file and split into a file per pallet with `subxt codegen --output-dir`.
+#
+# Each layout is built from scratch, and again after the `Balances` pallet changed, like it
+# does when regenerating the API for an upgraded runtime.
+#
+# Usage: scripts/codegen-build-times.sh
+
+set -euo pipefail
+
+ROOT=$(cd "$(dirname "$0")/.." && pwd)
+METADATA="$ROOT/tests/integration/node_runtime.scale"
+WORK=$(mktemp -d)
+trap 'rm -rf "$WORK"' EXIT
+
+cargo build --release --manifest-path "$ROOT/Cargo.toml" -p subxt-cli
+SUBXT="$ROOT/target/release/subxt"
+
+# a crate using the generated API, with the dependencies built up front
+new_crate() {
+    local dir="$WORK/$1"
+    mkdir -p "$dir/src"
+    cat > "$dir/Cargo.toml" <<EOF
+[package]
+name = "$1"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+subxt = { path = "$ROOT" }
+
+[workspace]
+EOF
+    echo > "$dir/src/lib.rs"
+    cargo build --quiet --manifest-path "$dir/Cargo.toml"
+    echo "pub mod api;" > "$dir/src/lib.rs"
+}
+
+# prints the seconds taken by building the crate
+build_time() {
+    local start end
+    start=$(date +%s.%N)
+    cargo build --quiet --manifest-path "$WORK/$1/Cargo.toml"
+    end=$(date +%s.%N)
+    awk "BEGIN { printf \"%.1f\", $end - $start }"
+}
+
+new_crate single
+"$SUBXT" codegen --file "$METADATA" > "$WORK/single/src/api.rs"
+rustfmt --edition 2018 "$WORK/single/src/api.rs"
+
+new_crate split
+"$SUBXT" codegen --file "$METADATA" --output-dir "$WORK/split/src/api"
+rustfmt --edition 2018 "$WORK/split/src/api/mod.rs"
+
+single_clean=$(build_time single)
+split_clean=$(build_time split)
+
+# the same change to the `Balances` pallet in both layouts
+sed -i '0,/pub mod balances {/s//\/\/ changed\n&/' "$WORK/single/src/api.rs"
+sed -i '1i // changed' "$WORK/split/src/api/balances.rs"
+single_changed=$(build_time single)
+split_changed=$(build_time split)
+
+printf '%-24s %12s %12s\n' "layout" "initial (s)" "changed (s)"
+printf '%-24s %12s %12s\n' "single file" "$single_clean" "$single_changed"
+printf '%-24s %12s %12s\n' "file per pallet" "$split_clean" "$split_changed"
@@ -0,0 +1,61 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Addresses of the variants of [`MultiAddress`] other than the account id, which convert into
+//! the address of a config generic over the address type, e.g. as arguments of the generated
+//! calls.
+
+use sp_runtime::MultiAddress;
+
+/// The index of an account in the Indices pallet of the runtime.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AccountIndex<Index>(pub Index);
+
+/// A raw address, of any format the lookup of the runtime supports.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawAddress(pub Vec<u8>);
+
+/// A 32 byte address, e.g. the hash of a public key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Address32(pub [u8; 32]);
+
+/// A 20 byte address, e.g. an Ethereum address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Address20(pub [u8; 20]);
+
+impl<AccountId, Index> From<AccountIndex<Index>> for MultiAddress<AccountId, Index> {
+    fn from(index: AccountIndex<Index>) -> Self {
+        MultiAddress::Index(index.0)
+    }
+}
+
+impl<AccountId, Index> From<RawAddress> for MultiAddress<AccountId, Index> {
+    fn from(address: RawAddress) -> Self {
+        MultiAddress::Raw(address.0)
+    }
+}
+
+impl<AccountId, Index> From<Address32> for MultiAddress<AccountId, Index> {
+    fn from(address: Address32) -> Self {
+        MultiAddress::Address32(address.0)
+    }
+}
+
+impl<AccountId, Index> From<Address20> for MultiAddress<AccountId, Index> {
+    fn from(address: Address20) -> Self {
+        MultiAddress::Address20(address.0)
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use codec::Decode;
+use scale_info::{
+    form::PortableForm,
+    TypeDef,
+    TypeDefPrimitive,
+};
+
+use crate::{
+    metadata::MetadataError,
+    Error,
+    Metadata,
+};
+
+/// The balances of an account, from the account data in the `System::Account` storage.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AccountBalance {
+    /// The balance which can be transferred or reserved.
+    pub free: u128,
+    /// The balance which is reserved, e.g. as a deposit.
+    pub reserved: u128,
+    /// The part of the balance which cannot be transferred, e.g. because it is locked for
+    /// staking. Older runtimes freeze balance for fees and for other uses separately, of
+    /// which this is the larger.
+    pub frozen: u128,
+    /// The flags of the account, only stored by newer runtimes.
+    pub flags: Option<u128>,
+}
+
+/// Decode the balances from the account info of the given type, skipping the fields before
+/// the account data, e.g. the nonce.
+pub(crate) fn decode_account_balance(
+    metadata: &Metadata,
+    type_id: u32,
+    input: &mut &[u8],
+) -> Result<AccountBalance, Error> {
+    let fields = match resolve_type_def(metadata, type_id)? {
+        TypeDef::Composite(composite) => composite.fields(),
+        _ => return Err("Account info is not a struct".into()),
+    };
+    for field in fields {
+        if field.name().map(String::as_str) != Some("data") {
+            decode_uint(metadata, field.ty().id(), input)?;
+            continue
+        }
+        let data_fields = match resolve_type_def(metadata, field.ty().id())? {
+            TypeDef::Composite(composite) => composite.fields(),
+            _ => return Err("Account data is not a struct".into()),
+        };
+        let mut balance = AccountBalance::default();
+        for data_field in data_fields {
+            let value = decode_uint(metadata, data_field.ty().id(), input)?;
+            match data_field.name().map(String::as_str) {
+                Some("free") => balance.free = value,
+                Some("reserved") => balance.reserved = value,
+                Some("frozen") | Some("misc_frozen") | Some("fee_frozen") => {
+                    balance.frozen = balance.frozen.max(value)
+                }
+                Some("flags") => balance.flags = Some(value),
+                _ => (),
+            }
+        }
+        return Ok(balance)
+    }
+    Err("Account info has no data".into())
+}
+
+/// Decode an unsigned integer of the given type, or of a struct wrapping one.
+fn decode_uint(
+    metadata: &Metadata,
+    type_id: u32,
+    input: &mut &[u8],
+) -> Result<u128, Error> {
+    match resolve_type_def(metadata, type_id)? {
+        TypeDef::Primitive(TypeDefPrimitive::U8) => Ok(u8::decode(input)?.into()),
+        TypeDef::Primitive(TypeDefPrimitive::U16) => Ok(u16::decode(input)?.into()),
+        TypeDef::Primitive(TypeDefPrimitive::U32) => Ok(u32::decode(input)?.into()),
+        TypeDef::Primitive(TypeDefPrimitive::U64) => Ok(u64::decode(input)?.into()),
+        TypeDef::Primitive(TypeDefPrimitive::U128) => Ok(u128::decode(input)?),
+        TypeDef::Composite(composite) if composite.fields().len() == 1 => {
+            decode_uint(metadata, composite.fields()[0].ty().id(), input)
+        }
+        _ => Err(format!("Unsupported account info field type {}", type_id).into()),
+    }
+}
+
+fn resolve_type_def(
+    metadata: &Metadata,
+    type_id: u32,
+) -> Result<&TypeDef<PortableForm>, Error> {
+    let ty = metadata
+        .resolve_type(type_id)
+        .ok_or(MetadataError::TypeNotFound(type_id))?;
+    Ok(ty.type_def())
+}
@@ -14,40 +14,197 @@
 // You should have received a copy of the GNU General Public License
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
-use futures::future;
+use codec::{
+    Decode,
+    Encode,
+};
+use frame_metadata::StorageEntryType;
+use futures::{
+    future::{
+        self,
+        Either,
+    },
+    lock::Mutex,
+    Future,
+};
+use futures_timer::Delay;
+use jsonrpsee_types::{
+    Error as RpcError,
+    Subscription,
+};
+use serde::Deserialize;
+use sp_core::crypto::{
+    Ss58AddressFormat,
+    Ss58Codec,
+};
 pub use sp_runtime::traits::SignedExtension;
+use sp_runtime::{
+    generic::Era,
+    traits::{
+        Header,
+        One,
+    },
+    ConsensusEngineId,
+    SaturatedConversion,
+};
 pub use sp_version::RuntimeVersion;
+use std::{
+    marker::PhantomData,
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::{
-    events::EventsDecoder,
+    balance::{
+        decode_account_balance,
+        AccountBalance,
+    },
+    events::{
+        EventRecord,
+        EventsDecoder,
+    },
     extrinsic::{
         self,
+        DecodedExtrinsic,
+        ExtrinsicParams,
         SignedExtra,
         Signer,
         UncheckedExtrinsic,
     },
+    metadata::MetadataError,
     rpc::{
         ExtrinsicSuccess,
+        ReconnectPolicy,
         Rpc,
         RpcClient,
+        RuntimeDispatchInfo,
         SystemProperties,
+        TransactionStatusStream,
+    },
+    storage::{
+        StorageClient,
+        StorageEntry,
+        StorageEntryKey,
+        StorageKeyPrefix,
+        StorageMapKey,
     },
-    storage::StorageClient,
-    AccountData,
+    subscription::SystemEvents,
     Call,
     Config,
+    Encoded,
     Error,
+    Event,
     ExtrinsicExtraData,
     Metadata,
+    RuntimeError,
 };
 
+/// The consensus engine id of the pre-runtime digest of blocks produced with BABE.
+const BABE_ENGINE_ID: ConsensusEngineId = *b"BABE";
+/// The consensus engine id of the pre-runtime digest of blocks produced with Aura.
+const AURA_ENGINE_ID: ConsensusEngineId = *b"aura";
+
+/// The `Session::Validators` storage entry, to look up the author of a block.
+struct SessionValidators<AccountId>(PhantomData<AccountId>);
+
+impl<AccountId: Decode> StorageEntry for SessionValidators<AccountId> {
+    const PALLET: &'static str = "Session";
+    const STORAGE: &'static str = "Validators";
+    type Value = Vec<AccountId>;
+    fn key(&self) -> StorageEntryKey {
+        StorageEntryKey::Plain
+    }
+}
+
+/// The `Aura::Authorities` storage entry, to look up the author of a block.
+///
+/// The authorities are public keys, decoded as the account ids of the keys.
+struct AuraAuthorities<AccountId>(PhantomData<AccountId>);
+
+impl<AccountId: Decode> StorageEntry for AuraAuthorities<AccountId> {
+    const PALLET: &'static str = "Aura";
+    const STORAGE: &'static str = "Authorities";
+    type Value = Vec<AccountId>;
+    fn key(&self) -> StorageEntryKey {
+        StorageEntryKey::Plain
+    }
+}
+
+/// The JSON-RPC error code of the transaction pool rejecting an invalid extrinsic.
+const POOL_INVALID_TX: i64 = 1010;
+/// The JSON-RPC error code of the transaction pool rejecting an extrinsic with too low a
+/// priority to replace the extrinsic with the same nonce in the pool.
+const POOL_TOO_LOW_PRIORITY: i64 = 1014;
+/// The JSON-RPC error code of the transaction pool rejecting an extrinsic which is already
+/// in the pool.
+const POOL_ALREADY_IMPORTED: i64 = 1013;
+/// The data of the [`POOL_INVALID_TX`] error of an extrinsic with an already used nonce.
+const STALE_TX_DATA: &str = "Transaction is outdated";
+/// The range of the JSON-RPC error codes of the author rpc rejecting a submitted extrinsic.
+const AUTHOR_ERROR_CODES: std::ops::Range<i64> = 1000..1100;
+
+/// The error object of a JSON-RPC error response.
+#[derive(Deserialize)]
+struct RpcErrorObject {
+    code: i64,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+}
+
+/// Returns the error object if the node answered a request with an error response, e.g.
+/// because the transaction pool rejected a submitted extrinsic.
+fn rpc_error_object(error: &Error) -> Option<RpcErrorObject> {
+    #[derive(Deserialize)]
+    struct ErrorResponse {
+        error: RpcErrorObject,
+    }
+
+    match error {
+        Error::Rpc(RpcError::Request(response)) => {
+            serde_json::from_str::<ErrorResponse>(response)
+                .ok()
+                .map(|response| response.error)
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if the node rejected an extrinsic when it was submitted, so that it never
+/// entered the transaction pool.
+fn is_submit_rejection(error: &Error) -> bool {
+    rpc_error_object(error).map_or(false, |error| {
+        AUTHOR_ERROR_CODES.contains(&error.code) && error.code != POOL_ALREADY_IMPORTED
+    })
+}
+
+/// Returns true if an extrinsic was rejected because of its nonce.
+fn is_nonce_error(error: &Error) -> bool {
+    // the pool rejects an extrinsic with an already used nonce as stale, and one with the
+    // nonce of another extrinsic in the pool as having a too low priority
+    //
+    // an extrinsic usurped in the pool by another one with the same nonce doesn't count,
+    // since the other one may have replaced it deliberately, e.g. to cancel it
+    rpc_error_object(error).map_or(false, |error| {
+        match error.code {
+            POOL_INVALID_TX => {
+                error.data.as_ref().and_then(|data| data.as_str()) == Some(STALE_TX_DATA)
+            }
+            POOL_TOO_LOW_PRIORITY => true,
+            _ => false,
+        }
+    })
+}
+
 /// ClientBuilder for constructing a Client.
 #[derive(Default)]
 pub struct ClientBuilder {
     url: Option<String>,
+    fallback_urls: Vec<String>,
     client: Option<RpcClient>,
     page_size: Option<u32>,
     accept_weak_inclusion: bool,
+    reconnect_policy: Option<ReconnectPolicy>,
+    ss58_format: Option<u16>,
 }
 
 impl ClientBuilder {
@@ -55,9 +212,12 @@ impl ClientBuilder {
     pub fn new() -> Self {
         Self {
             url: None,
+            fallback_urls: Vec::new(),
             client: None,
             page_size: None,
             accept_weak_inclusion: false,
+            reconnect_policy: None,
+            ss58_format: None,
         }
     }
 
@@ -73,6 +233,17 @@ impl ClientBuilder {
         self
     }
 
+    /// Set multiple substrate WebSocket rpc addresses, to fail over to the next one when the
+    /// connection to the active one fails.
+    ///
+    /// Implies reconnecting with the default [`ReconnectPolicy`] if none is set.
+    pub fn set_urls<P: Into<String>>(mut self, urls: Vec<P>) -> Self {
+        let mut urls = urls.into_iter().map(Into::into);
+        self.url = urls.next();
+        self.fallback_urls = urls.collect();
+        self
+    }
+
     /// Set the page size.
     pub fn set_page_size(mut self, size: u32) -> Self {
         self.page_size = Some(size);
@@ -85,13 +256,43 @@ impl ClientBuilder {
         self
     }
 
+    /// Re-establish the WebSocket connection to the url according to the given policy when
+    /// it is dropped.
+    ///
+    /// Has no effect if a jsonrpsee client is set, or the url is not a WebSocket url.
+    pub fn set_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = Some(policy);
+        self
+    }
+
+    /// Set the SS58 address format of the chain, instead of the format reported by the node
+    /// in its system properties.
+    pub fn set_ss58_format(mut self, format: u16) -> Self {
+        self.ss58_format = Some(format);
+        self
+    }
+
     /// Creates a new Client.
     pub async fn build<T: Config>(self) -> Result<Client<T>, Error> {
         let client = if let Some(client) = self.client {
             client
         } else {
             let url = self.url.as_deref().unwrap_or("ws://127.0.0.1:9944");
-            RpcClient::try_from_url(url).await?
+            if !self.fallback_urls.is_empty() {
+                let mut urls = vec![url.to_owned()];
+                urls.extend(self.fallback_urls);
+                let policy = self.reconnect_policy.unwrap_or_default();
+                RpcClient::try_reconnecting_from_urls(urls, policy).await?
+            } else {
+                match self.reconnect_policy {
+                    Some(policy)
+                        if url.starts_with("ws://") || url.starts_with("wss://") =>
+                    {
+                        RpcClient::try_reconnecting_from_url(url, policy).await?
+                    }
+                    _ => RpcClient::try_from_url(url).await?,
+                }
+            }
         };
         let mut rpc = Rpc::new(client);
         if self.accept_weak_inclusion {
@@ -107,13 +308,17 @@ impl ClientBuilder {
         let metadata = metadata?;
 
         let events_decoder = EventsDecoder::new(metadata.clone());
+        let properties = properties?;
+        // fall back to the generic substrate format if the node does not report one
+        let ss58_format = self.ss58_format.or(properties.ss58_format).unwrap_or(42);
 
         Ok(Client {
             rpc,
             genesis_hash: genesis_hash?,
             metadata,
             events_decoder,
-            properties: properties.unwrap_or_else(|_| Default::default()),
+            properties,
+            ss58_format,
             runtime_version: runtime_version?,
             iter_page_size: self.page_size.unwrap_or(10),
         })
@@ -121,12 +326,15 @@ impl ClientBuilder {
 }
 
 /// Client to interface with a substrate node.
+///
+/// Cloning the client is cheap, and all clones share the same connection to the node.
 pub struct Client<T: Config> {
     rpc: Rpc<T>,
     genesis_hash: T::Hash,
     metadata: Metadata,
     events_decoder: EventsDecoder<T>,
     properties: SystemProperties,
+    ss58_format: u16,
     runtime_version: RuntimeVersion,
     // _marker: PhantomData<(fn() -> T::Signature, T::Extra)>,
     iter_page_size: u32,
@@ -140,6 +348,7 @@ impl<T: Config> Clone for Client<T> {
             metadata: self.metadata.clone(),
             events_decoder: self.events_decoder.clone(),
             properties: self.properties.clone(),
+            ss58_format: self.ss58_format,
             runtime_version: self.runtime_version.clone(),
             iter_page_size: self.iter_page_size,
         }
@@ -157,11 +366,51 @@ impl<T: Config> Client<T> {
         &self.metadata
     }
 
+    /// Returns the runtime version the client was constructed with.
+    pub fn runtime_version(&self) -> &RuntimeVersion {
+        &self.runtime_version
+    }
+
     /// Returns the system properties
     pub fn properties(&self) -> &SystemProperties {
         &self.properties
     }
 
+    /// Returns the SS58 address format of the chain.
+    ///
+    /// This is the format set with [`ClientBuilder::set_ss58_format`], or else the
+    /// [`SystemProperties::ss58_format`] of the node, which defaults to the generic substrate
+    /// format 42 if the node does not report one.
+    pub fn ss58_format(&self) -> u16 {
+        self.ss58_format
+    }
+
+    /// Format an account id as an SS58 address of the chain, e.g. to display it.
+    pub fn account_id_to_ss58(&self, account_id: &T::AccountId) -> String
+    where
+        T::AccountId: Ss58Codec,
+    {
+        account_id.to_ss58check_with_version(Ss58AddressFormat::custom(self.ss58_format))
+    }
+
+    /// Parse an SS58 encoded address into an account id, e.g. to pass it to a call.
+    ///
+    /// Returns an error if the address is not valid, or [`Error::WrongNetwork`] if it is
+    /// encoded for another network than the [`ss58_format`](Self::ss58_format) of the chain,
+    /// e.g. to reject an address before transferring to it.
+    pub fn account_id_from_ss58(&self, address: &str) -> Result<T::AccountId, Error>
+    where
+        T::AccountId: Ss58Codec,
+    {
+        let (account_id, format) = T::AccountId::from_ss58check_with_version(address)
+            .map_err(|err| format!("Invalid SS58 address {}: {:?}", address, err))?;
+        let prefix = u16::from(format);
+        if prefix != self.ss58_format {
+            return Err(Error::WrongNetwork(self.ss58_format, prefix))
+        }
+        Ok(account_id)
+    }
+
     /// Returns the rpc client.
     pub fn rpc(&self) -> &Rpc<T> {
         &self.rpc
@@ -184,12 +433,218 @@ impl<T: Config> Client<T> {
     pub fn events_decoder(&self) -> &EventsDecoder<T> {
         &self.events_decoder
     }
+
+    /// Fetch and decode the events of the block with the given hash.
+    pub async fn events_at(&self, hash: T::Hash) -> Result<Vec<EventRecord>, Error> {
+        let key = SystemEvents::new().into();
+        let events = match self.storage().fetch_raw(key, Some(hash)).await? {
+            Some(data) => self.events_decoder.decode_events(&mut &data.0[..])?,
+            None => Vec::new(),
+        };
+        Ok(events
+            .into_iter()
+            .map(|(phase, event)| EventRecord { phase, event })
+            .collect())
+    }
+
+    /// Fetch the balances of an account, decoded from the `System::Account` storage with the
+    /// layout of its account data in the metadata.
+    pub async fn account_balance(
+        &self,
+        account_id: &T::AccountId,
+        hash: Option<T::Hash>,
+    ) -> Result<AccountBalance, Error> {
+        let storage = self.metadata.pallet("System")?.storage("Account")?;
+        let (hasher, value_type) = match &storage.ty {
+            StorageEntryType::Map { hashers, value, .. } if hashers.len() == 1 => {
+                (hashers[0].clone(), value.id())
+            }
+            _ => return Err(MetadataError::StorageTypeError.into()),
+        };
+        let key = StorageEntryKey::Map(vec![StorageMapKey::new(account_id, hasher)])
+            .final_key(StorageKeyPrefix::from_names("System", "Account"));
+        let data = match self.storage().fetch_raw(key, hash).await? {
+            Some(data) => data.0,
+            None => storage.default.clone(),
+        };
+        decode_account_balance(&self.metadata, value_type, &mut &data[..])
+    }
+
+    /// Find the author of the block with the given header.
+    ///
+    /// The author was chosen from the authorities before the block, so they are looked up in
+    /// the storage at its parent: in `Session::Validators` by the authority index of the BABE
+    /// pre-runtime digest of the block, or in `Aura::Authorities` by the slot of its Aura
+    /// pre-runtime digest. Aura authorities are public keys, which are returned as the
+    /// account ids of the keys. Returns `None` if the block has neither digest.
+    pub async fn block_author(
+        &self,
+        header: &T::Header,
+    ) -> Result<Option<T::AccountId>, Error> {
+        let mut babe_authority_index = None;
+        let mut aura_slot = None;
+        for log in header.digest().logs() {
+            match log.as_pre_runtime() {
+                Some((BABE_ENGINE_ID, mut data)) => {
+                    // all variants of the BABE `PreDigest` start with the authority index
+                    let _variant = u8::decode(&mut data)?;
+                    babe_authority_index = Some(u32::decode(&mut data)?);
+                }
+                Some((AURA_ENGINE_ID, mut data)) => {
+                    aura_slot = Some(u64::decode(&mut data)?)
+                }
+                _ => (),
+            }
+        }
+
+        let parent = Some(*header.parent_hash());
+        let (authorities, index) = match (babe_authority_index, aura_slot) {
+            (Some(index), _) => {
+                let validators = self
+                    .storage()
+                    .fetch(&SessionValidators(PhantomData), parent)
+                    .await?
+                    .unwrap_or_default();
+                (validators, index as usize)
+            }
+            (None, Some(slot)) => {
+                let authorities = self
+                    .storage()
+                    .fetch(&AuraAuthorities(PhantomData), parent)
+                    .await?
+                    .unwrap_or_default();
+                if authorities.is_empty() {
+                    return Ok(None)
+                }
+                let index = (slot % authorities.len() as u64) as usize;
+                (authorities, index)
+            }
+            (None, None) => return Ok(None),
+        };
+        Ok(authorities.get(index).cloned())
+    }
+
+    /// Fetch the block with the given hash, and decode its extrinsics with their calls decoded
+    /// into the outer call enum `C` of the runtime.
+    ///
+    /// Returns `None` if the block is not found.
+    pub async fn block_extrinsics<C>(
+        &self,
+        hash: T::Hash,
+    ) -> Result<Option<Vec<DecodedExtrinsic<T, C>>>, Error>
+    where
+        T: ExtrinsicExtraData<T>,
+        T::Signature: Decode,
+        C: Decode,
+    {
+        let block = match self.rpc.block(Some(hash)).await? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let extrinsics = block
+            .block
+            .extrinsics
+            .iter()
+            .map(extrinsic::decode_extrinsic::<T, C>)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(extrinsics))
+    }
+
+    /// Fetch the extrinsics currently in the transaction pool of the node, with their calls
+    /// decoded into the outer call enum `C` of the runtime.
+    pub async fn pending_extrinsics<C>(
+        &self,
+    ) -> Result<Vec<DecodedExtrinsic<T, C>>, Error>
+    where
+        T: ExtrinsicExtraData<T>,
+        T::Signature: Decode,
+        C: Decode,
+    {
+        let extrinsics = self.rpc.pending_extrinsics().await?;
+        let extrinsics = extrinsics
+            .iter()
+            .map(|bytes| DecodedExtrinsic::<T, C>::decode(&mut &bytes[..]))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(extrinsics)
+    }
+
+    /// Fetch the current metadata and runtime version from the node, replacing the ones the
+    /// client was constructed with, e.g. after a runtime upgrade.
+    ///
+    /// If fetching either of them fails, both are kept.
+    ///
+    /// The metadata is used for decoding events and errors and for looking up storage
+    /// defaults. Calls, events and storage entries generated from the old metadata are not
+    /// updated, so they may no longer match the upgraded runtime.
+    pub async fn update_metadata(&mut self) -> Result<(), Error> {
+        let (metadata, runtime_version) =
+            future::join(self.rpc.metadata(), self.rpc.runtime_version(None)).await;
+        // replace neither if either failed, so that extrinsics are not signed with a
+        // runtime version which does not match the metadata
+        let (metadata, runtime_version) = (metadata?, runtime_version?);
+        self.set_metadata(metadata);
+        self.runtime_version = runtime_version;
+        Ok(())
+    }
+
+    /// Replace the metadata the client was constructed with, e.g. with metadata fetched by
+    /// other means after a runtime upgrade.
+    ///
+    /// The constants generated by the `subxt` macro are read from the current metadata, so
+    /// they reflect the replaced metadata.
+    pub fn set_metadata(&mut self, metadata: Metadata) {
+        self.events_decoder = EventsDecoder::new(metadata.clone());
+        self.metadata = metadata;
+    }
+
+    /// Wait for a runtime upgrade announced by the given subscription, then update the
+    /// metadata as in [`Client::update_metadata`].
+    ///
+    /// Runtime versions with the same spec version as the current one are skipped.
+    pub async fn update_metadata_on_upgrade(
+        &mut self,
+        subscription: &mut Subscription<RuntimeVersion>,
+    ) -> Result<(), Error> {
+        while let Some(runtime_version) = subscription.next().await? {
+            if runtime_version.spec_version != self.runtime_version.spec_version {
+                return self.update_metadata().await
+            }
+        }
+        Err("Runtime version subscription terminated".into())
+    }
+}
+
+/// Await the future, failing with [`Error::Timeout`] if it does not complete within the
+/// timeout.
+///
+/// On timeout the future is dropped, closing any subscriptions it holds.
+async fn with_timeout<F, R>(timeout: Option<Duration>, future: F) -> Result<R, Error>
+where
+    F: Future<Output = Result<R, Error>>,
+{
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return future.await,
+    };
+    futures::pin_mut!(future);
+    match future::select(future, Delay::new(timeout)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(Error::Timeout),
+    }
 }
 
 /// A constructed call ready to be signed and submitted.
+///
+/// The call can be inspected and encoded before signing, and the extrinsic can be cloned to
+/// e.g. estimate its fee or dry run it and then submit it.
+#[derive(Clone)]
 pub struct SubmittableExtrinsic<'a, T: Config, C> {
     client: &'a Client<T>,
     call: C,
+    mortality: Option<(u64, T::Hash)>,
+    tip: u128,
+    nonce: Option<T::Index>,
+    timeout: Option<Duration>,
 }
 
 impl<'a, T, C> SubmittableExtrinsic<'a, T, C>
@@ -199,7 +654,79 @@ where
 {
     /// Create a new [`SubmittableExtrinsic`].
     pub fn new(client: &'a Client<T>, call: C) -> Self {
-        Self { client, call }
+        Self {
+            client,
+            call,
+            mortality: None,
+            tip: 0,
+            nonce: None,
+            timeout: None,
+        }
+    }
+
+    /// Returns the call of the extrinsic.
+    pub fn call(&self) -> &C {
+        &self.call
+    }
+
+    /// Encodes the call of the extrinsic, prefixed by the indices of its pallet and of the
+    /// call in the metadata of the client, i.e. as it is included in the signed extrinsic.
+    pub fn encoded_call(&self) -> Result<Encoded, Error> {
+        let call = self
+            .client
+            .metadata()
+            .pallet(C::PALLET)
+            .and_then(|pallet| pallet.encode_call(&self.call))?;
+        Ok(call)
+    }
+
+    /// Make the extrinsic mortal, so that it is only valid for `period` blocks after the
+    /// `checkpoint` block.
+    ///
+    /// By default an extrinsic is immortal, i.e. valid forever.
+    pub fn mortal(mut self, period: u64, checkpoint: T::Hash) -> Self {
+        self.mortality = Some((period, checkpoint));
+        self
+    }
+
+    /// Add a tip for the block author, to increase the priority of the extrinsic.
+    ///
+    /// Defaults to zero.
+    pub fn tip(mut self, tip: u128) -> Self {
+        self.tip = tip;
+        self
+    }
+
+    /// Use the given nonce for the extrinsic, rather than the one provided by the signer or
+    /// fetched from the node.
+    ///
+    /// Allows submitting many extrinsics from the same account without waiting for each one
+    /// to be included.
+    pub fn nonce(mut self, nonce: T::Index) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Give up watching the extrinsic with [`Error::Timeout`] if it has not reached a final
+    /// status within `timeout`, e.g. because it is stuck in the transaction pool.
+    ///
+    /// The extrinsic may still be included after the timeout, since it is not removed from
+    /// the pool. By default the extrinsic is watched until it reaches a final status.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Check that the arguments of the call match the metadata of the node.
+    ///
+    /// Returns [`Error::MetadataMismatch`] if the call was generated from metadata in which
+    /// its arguments differ, e.g. from an older runtime version, in which case submitting it
+    /// would fail to decode on the node or dispatch a different call.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.client.metadata().call_hash::<C>()? != C::CALL_HASH {
+            return Err(Error::MetadataMismatch(C::PALLET, C::FUNCTION))
+        }
+        Ok(())
     }
 
     /// Creates and signs an extrinsic and submits it to the chain.
@@ -214,10 +741,104 @@ where
         <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
     {
         let extrinsic = self.create_signed(signer).await?;
-        self.client
+        let watch = self
+            .client
             .rpc()
-            .submit_and_watch_extrinsic(extrinsic, self.client.events_decoder())
-            .await
+            .submit_and_watch_extrinsic(extrinsic, self.client.events_decoder());
+        with_timeout(self.timeout, watch).await
+    }
+
+    /// Like [`Self::sign_and_submit_then_watch`], but returns the first event of the given
+    /// type triggered by the extrinsic.
+    ///
+    /// Returns an error if the extrinsic failed, or did not trigger such an event.
+    pub async fn submit_and_find_event<E: Event>(
+        self,
+        signer: &(dyn Signer<T> + Send + Sync),
+    ) -> Result<E, Error>
+    where
+        <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
+    {
+        let result = self.sign_and_submit_then_watch(signer).await?;
+        result
+            .find_event::<E>()?
+            .ok_or_else(|| format!("Event {}::{} not found", E::PALLET, E::EVENT).into())
+    }
+
+    /// Like [`Self::sign_and_submit_then_watch`], but if the extrinsic is rejected because of
+    /// its nonce, e.g. because the nonce was already used by an extrinsic submitted
+    /// concurrently, refetches the nonce from the node and resubmits it, up to `max_retries`
+    /// times.
+    ///
+    /// Other failures, e.g. the extrinsic failing to dispatch because of an insufficient
+    /// balance, are returned without retrying. So is an extrinsic which was replaced in the
+    /// pool by another one with the same nonce, as
+    /// [`TransactionError::Usurped`](crate::TransactionError::Usurped), since the other one
+    /// may have been submitted to cancel it. Neither is an extrinsic with a nonce which is
+    /// too high retried, since it waits in the pool for the missing nonces rather than being
+    /// rejected.
+    pub async fn sign_and_submit_then_watch_retry(
+        mut self,
+        signer: &(dyn Signer<T> + Send + Sync),
+        max_retries: usize,
+    ) -> Result<ExtrinsicSuccess<T>, Error>
+    where
+        <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
+    {
+        let mut retries = 0;
+        loop {
+            let extrinsic = self.create_signed(signer).await?;
+            let watch = self
+                .client
+                .rpc()
+                .submit_and_watch_extrinsic(extrinsic, self.client.events_decoder());
+            match with_timeout(self.timeout, watch).await {
+                Err(error) if retries < max_retries && is_nonce_error(&error) => {
+                    log::info!("Resubmitting extrinsic with a new nonce: {}", error);
+                    retries += 1;
+                    let nonce =
+                        self.client.rpc().account_nonce(signer.account_id()).await?;
+                    self.nonce = Some(nonce);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Creates and signs an extrinsic and submits it to the chain.
+    ///
+    /// Returns when the block in which the extrinsic was included has been finalized, together
+    /// with any events which were triggered by the extrinsic. Unlike
+    /// [`Self::sign_and_submit_then_watch`] this is regardless of whether the client was
+    /// configured to [`ClientBuilder::accept_weak_inclusion`].
+    pub async fn sign_and_submit_then_watch_finalized(
+        self,
+        signer: &(dyn Signer<T> + Send + Sync),
+    ) -> Result<ExtrinsicSuccess<T>, Error>
+    where
+        <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
+    {
+        let extrinsic = self.create_signed(signer).await?;
+        let watch = self.client.rpc().submit_and_watch_extrinsic_finalized(
+            extrinsic,
+            self.client.events_decoder(),
+        );
+        with_timeout(self.timeout, watch).await
+    }
+
+    /// Creates and signs an extrinsic and submits it to the chain.
+    ///
+    /// Returns a stream of all status updates of the extrinsic, e.g. to report its progress
+    /// from the pool into a finalized block. The stream ends after a final status.
+    pub async fn sign_and_submit_and_watch_stream(
+        self,
+        signer: &(dyn Signer<T> + Send + Sync),
+    ) -> Result<TransactionStatusStream<T>, Error>
+    where
+        <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
+    {
+        let extrinsic = self.create_signed(signer).await?;
+        self.client.rpc().watch_extrinsic_status(extrinsic).await
     }
 
     /// Creates and signs an extrinsic and submits to the chain for block inclusion.
@@ -227,7 +848,8 @@ where
     /// # Note
     ///
     /// Success does not mean the extrinsic has been included in the block, just that it is valid
-    /// and has been included in the transaction pool.
+    /// and has been included in the transaction pool. Unlike
+    /// [`Self::sign_and_submit_then_watch`] no subscription is made to the extrinsic status.
     pub async fn sign_and_submit(
         self,
         signer: &(dyn Signer<T> + Send + Sync),
@@ -239,6 +861,49 @@ where
         self.client.rpc().submit_extrinsic(extrinsic).await
     }
 
+    /// Creates and signs an extrinsic and executes a dry run of it against the state at the
+    /// given block, without submitting it.
+    ///
+    /// Returns `Ok` if the extrinsic would be dispatched successfully, otherwise the error
+    /// with which it would be rejected or fail.
+    pub async fn dry_run(
+        &self,
+        signer: &(dyn Signer<T> + Send + Sync),
+        at: Option<T::Hash>,
+    ) -> Result<(), Error>
+    where
+        <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
+    {
+        let extrinsic = self.create_signed(signer).await?;
+        let result = self.client.rpc().dry_run(&extrinsic.encode(), at).await?;
+        match result? {
+            Ok(()) => Ok(()),
+            Err(dispatch_error) => {
+                let error =
+                    RuntimeError::from_dispatch(self.client.metadata(), dispatch_error)?;
+                Err(error.into())
+            }
+        }
+    }
+
+    /// Creates and signs an extrinsic and queries the fee which would be charged for it,
+    /// without submitting it.
+    ///
+    /// The fee estimate does not include any tip.
+    pub async fn partial_fee_estimate(
+        &self,
+        signer: &(dyn Signer<T> + Send + Sync),
+    ) -> Result<RuntimeDispatchInfo, Error>
+    where
+        <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
+    {
+        let extrinsic = self.create_signed(signer).await?;
+        self.client
+            .rpc()
+            .query_info(&extrinsic.encode(), None)
+            .await
+    }
+
     /// Creates a signed extrinsic.
     pub async fn create_signed(
         &self,
@@ -247,25 +912,13 @@ where
     where
         <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
     {
-        let account_nonce = if let Some(nonce) = signer.nonce() {
+        let account_nonce = if let Some(nonce) = self.nonce.or_else(|| signer.nonce()) {
             nonce
         } else {
-            let account_storage_entry =
-                <<T as ExtrinsicExtraData<T>>::AccountData as AccountData<T>>::storage_entry(signer.account_id().clone());
-            let account_data = self
-                .client
-                .storage()
-                .fetch_or_default(&account_storage_entry, None)
-                .await?;
-            <<T as ExtrinsicExtraData<T>>::AccountData as AccountData<T>>::nonce(
-                &account_data,
-            )
+            self.client.rpc().account_nonce(signer.account_id()).await?
         };
-        let call = self
-            .client
-            .metadata()
-            .pallet(C::PALLET)
-            .and_then(|pallet| pallet.encode_call(&self.call))?;
+        let call = self.encoded_call()?;
+        let params = self.extrinsic_params().await?;
 
         let signed = extrinsic::create_signed(
             &self.client.runtime_version,
@@ -273,8 +926,125 @@ where
             account_nonce,
             call,
             signer,
+            params,
         )
         .await?;
         Ok(signed)
     }
+
+    async fn extrinsic_params(&self) -> Result<ExtrinsicParams<T>, Error> {
+        let mut params = ExtrinsicParams {
+            tip: self.tip,
+            ..Default::default()
+        };
+        if let Some((period, checkpoint)) = self.mortality {
+            let header = self
+                .client
+                .rpc()
+                .header(Some(checkpoint))
+                .await?
+                .ok_or_else(|| format!("Checkpoint block {:?} not found", checkpoint))?;
+            let number = (*header.number()).saturated_into::<u64>();
+            params.era = Era::mortal(period, number);
+            params.era_checkpoint = Some(checkpoint);
+        }
+        Ok(params)
+    }
+}
+
+/// Submits extrinsics of a single signer, tracking the nonce of the signer locally so that
+/// extrinsics can be submitted concurrently without waiting for the previous ones to land.
+///
+/// The nonce is fetched from the node for the first extrinsic, and again after the node
+/// rejected a submitted extrinsic, which would otherwise leave a gap in the nonces. Other
+/// failures keep the tracked nonce, since the extrinsic may have entered the pool and so
+/// used its nonce, e.g. if watching it timed out; use [`Self::resync`] once the extrinsics
+/// in flight have settled to fetch the nonce again.
+pub struct TransactionManager<T: Config> {
+    client: Client<T>,
+    signer: Arc<dyn Signer<T> + Send + Sync>,
+    next_nonce: Arc<Mutex<Option<T::Index>>>,
+}
+
+impl<T: Config> Clone for TransactionManager<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            signer: self.signer.clone(),
+            next_nonce: self.next_nonce.clone(),
+        }
+    }
+}
+
+impl<T> TransactionManager<T>
+where
+    T: Config + ExtrinsicExtraData<T>,
+{
+    /// Creates a new [`TransactionManager`], submitting extrinsics signed by the signer.
+    pub fn new<S: Signer<T> + Send + Sync + 'static>(
+        client: Client<T>,
+        signer: S,
+    ) -> Self {
+        Self {
+            client,
+            signer: Arc::new(signer),
+            next_nonce: Default::default(),
+        }
+    }
+
+    /// Returns the nonce of the next extrinsic, if it is known without asking the node.
+    pub async fn next_nonce(&self) -> Option<T::Index> {
+        *self.next_nonce.lock().await
+    }
+
+    /// Forget the tracked nonce, to fetch it from the node for the next extrinsic.
+    pub async fn resync(&self) {
+        *self.next_nonce.lock().await = None;
+    }
+
+    /// Signs the call with the next nonce and submits it to the chain.
+    ///
+    /// Returns when the extrinsic has successfully been included in a block, like
+    /// [`SubmittableExtrinsic::sign_and_submit_then_watch`]. The nonce is taken before the
+    /// extrinsic is submitted, so that the futures of several calls can be awaited
+    /// concurrently.
+    pub async fn submit<C: Call + Send + Sync>(
+        &self,
+        call: C,
+    ) -> Result<ExtrinsicSuccess<T>, Error>
+    where
+        <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static
+    {
+        let extrinsic = {
+            let mut next_nonce = self.next_nonce.lock().await;
+            let nonce = match *next_nonce {
+                Some(nonce) => nonce,
+                None => {
+                    self.client
+                        .rpc()
+                        .account_nonce(self.signer.account_id())
+                        .await?
+                }
+            };
+            let extrinsic = SubmittableExtrinsic::new(&self.client, call)
+                .nonce(nonce)
+                .create_signed(&*self.signer)
+                .await?;
+            *next_nonce = Some(nonce + One::one());
+            extrinsic
+        };
+        let result = self
+            .client
+            .rpc()
+            .submit_and_watch_extrinsic(extrinsic, self.client.events_decoder())
+            .await;
+        // only an extrinsic which never entered the pool certainly left its nonce unused
+        if let Err(error) = &result {
+            if is_submit_rejection(error) {
+                log::info!("Resyncing nonce after rejected submission: {}", error);
+                self.resync().await
+            }
+        }
+        result
+    }
 }
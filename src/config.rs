@@ -14,10 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{
-    SignedExtra,
-    StorageEntry,
-};
+use crate::SignedExtra;
 use codec::{
     Codec,
     Encode,
@@ -26,6 +23,7 @@ use codec::{
 use core::fmt::Debug;
 use sp_runtime::traits::{
     AtLeast32Bit,
+    AtLeast32BitUnsigned,
     Extrinsic,
     Hash,
     Header,
@@ -38,12 +36,19 @@ use sp_runtime::traits::{
 pub trait Config: Clone + Sized + Send + Sync + 'static {
     /// Account index (aka nonce) type. This stores the number of previous
     /// transactions associated with a sender account.
-    type Index: Parameter + Member + Default + AtLeast32Bit + Copy + scale_info::TypeInfo;
+    type Index: Parameter
+        + Member
+        + MaybeSerializeDeserialize
+        + Default
+        + AtLeast32Bit
+        + Copy
+        + scale_info::TypeInfo;
 
     /// The block number type used by the runtime.
     type BlockNumber: Parameter
         + Member
         + Default
+        + AtLeast32BitUnsigned
         + Copy
         + core::hash::Hash
         + core::str::FromStr;
@@ -61,13 +66,25 @@ pub trait Config: Clone + Sized + Send + Sync + 'static {
         + scale_info::TypeInfo;
 
     /// The hashing system (algorithm) being used in the runtime (e.g. Blake2).
+    ///
+    /// Used to hash extrinsics, e.g. to find a submitted extrinsic in a block, and headers.
+    /// Storage keys are hashed with the hashers of the storage entries in the metadata instead.
     type Hashing: Hash<Output = Self::Hash>;
 
     /// The user account identifier type for the runtime.
-    type AccountId: Parameter + Member;
+    type AccountId: Parameter + Member + serde::Serialize;
 
     /// The address type. This instead of `<frame_system::Trait::Lookup as StaticLookup>::Source`.
-    type Address: Codec + Clone + PartialEq;
+    ///
+    /// E.g. [`sp_runtime::MultiAddress`], whose variants choose how the runtime looks up the
+    /// account: by its `Id`, by its `Index` in the Indices pallet, or by a `Raw`, `Address32`
+    /// or `Address20` address, as far as the lookup of the runtime supports them.
+    ///
+    /// The generated transaction api takes account addresses as anything converting into
+    /// this type, e.g. an account id, or for a `MultiAddress` one of the
+    /// [`AccountIndex`](crate::AccountIndex), [`RawAddress`](crate::RawAddress),
+    /// [`Address32`](crate::Address32) and [`Address20`](crate::Address20) addresses.
+    type Address: Codec + Clone + PartialEq + Send + Sync;
 
     /// The block header.
     type Header: Parameter
@@ -85,21 +102,8 @@ pub trait Config: Clone + Sized + Send + Sync + 'static {
 pub trait Parameter: Codec + EncodeLike + Clone + Eq + Debug {}
 impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + Debug {}
 
-/// Trait to fetch data about an account.
-///
-/// Should be implemented on a type implementing `StorageEntry`,
-/// usually generated by the `subxt` macro.
-pub trait AccountData<T: Config>: StorageEntry {
-    /// Create a new storage entry key from the account id.
-    fn storage_entry(account_id: T::AccountId) -> Self;
-    /// Get the nonce from the storage entry value.
-    fn nonce(result: &<Self as StorageEntry>::Value) -> T::Index;
-}
-
 /// Trait to configure the extra data for an extrinsic.
 pub trait ExtrinsicExtraData<T: Config> {
-    /// The type of the [`StorageEntry`] which can be used to retrieve an account nonce.
-    type AccountData: AccountData<T>;
     /// The type of extra data and additional signed data to be included in a transaction.
     type Extra: SignedExtra<T> + Send + Sync + 'static;
 }
@@ -22,11 +22,14 @@ use crate::{
     },
     Metadata,
 };
+use codec::Decode;
 use jsonrpsee_types::Error as RequestError;
 use sp_core::crypto::SecretStringError;
 use sp_runtime::{
     transaction_validity::TransactionValidityError,
+    ArithmeticError,
     DispatchError,
+    TokenError,
 };
 use thiserror::Error;
 
@@ -63,6 +66,20 @@ pub enum Error {
     /// Events decoding error.
     #[error("Events decoding error: {0}")]
     EventsDecoding(#[from] EventsDecodingError),
+    /// The arguments of a call do not match the metadata of the node, e.g. because the code
+    /// was generated from the metadata of another runtime version.
+    #[error("Call {0}::{1} does not match the metadata of the node")]
+    MetadataMismatch(&'static str, &'static str),
+    /// An SS58 address is encoded for another network than the one of the chain, with the
+    /// expected and the found network.
+    #[error("SS58 address is for network {1}, expected network {0}")]
+    WrongNetwork(u16, u16),
+    /// Transaction error.
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+    /// Timed out, e.g. waiting for an extrinsic to be included.
+    #[error("Timed out")]
+    Timeout,
     /// Other error.
     #[error("Other error: {0}")]
     Other(String),
@@ -110,6 +127,12 @@ pub enum RuntimeError {
     /// Cannot lookup.
     #[error("Cannot lookup some information required to validate the transaction.")]
     CannotLookup,
+    /// An error to do with tokens.
+    #[error("Token error: {0:?}")]
+    Token(TokenError),
+    /// An arithmetic error.
+    #[error("Arithmetic error: {0:?}")]
+    Arithmetic(ArithmeticError),
     /// Other error.
     #[error("Other error: {0}")]
     Other(String),
@@ -124,29 +147,48 @@ impl RuntimeError {
         match error {
             DispatchError::Module {
                 index,
-                error,
+                error: error_index,
                 message: _,
             } => {
-                let error = metadata.error(index, error)?;
+                let error = metadata.error(index, error_index)?;
                 Ok(Self::Module(PalletError {
                     pallet: error.pallet().to_string(),
                     error: error.error().to_string(),
                     description: error.description().to_vec(),
+                    pallet_index: index,
+                    error_index,
                 }))
             }
             DispatchError::BadOrigin => Ok(Self::BadOrigin),
             DispatchError::CannotLookup => Ok(Self::CannotLookup),
             DispatchError::ConsumerRemaining => Ok(Self::ConsumerRemaining),
             DispatchError::NoProviders => Ok(Self::NoProviders),
-            DispatchError::Arithmetic(_math_error) => {
-                Ok(Self::Other("math_error".into()))
-            }
-            DispatchError::Token(_token_error) => Ok(Self::Other("token error".into())),
+            DispatchError::Arithmetic(math_error) => Ok(Self::Arithmetic(math_error)),
+            DispatchError::Token(token_error) => Ok(Self::Token(token_error)),
             DispatchError::Other(msg) => Ok(Self::Other(msg.to_string())),
         }
     }
 }
 
+/// The transaction pool gave up on a submitted extrinsic, as reported by its status.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum TransactionError {
+    /// The extrinsic is no longer valid in the current state.
+    #[error("Extrinsic is invalid")]
+    Invalid,
+    /// The extrinsic was replaced in the pool by the extrinsic with the given hash, which
+    /// provides the same tags, e.g. has the same sender and nonce.
+    #[error("Extrinsic was usurped by 0x{}", hex::encode(.0))]
+    Usurped(Vec<u8>),
+    /// The extrinsic was dropped from the pool because of its limits.
+    #[error("Extrinsic was dropped")]
+    Dropped,
+    /// The node stopped watching for the finality of the block with the given hash, in
+    /// which the extrinsic was included.
+    #[error("Finality of block 0x{} timed out", hex::encode(.0))]
+    FinalityTimeout(Vec<u8>),
+}
+
 /// Module error.
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
 #[error("{error} from {pallet}")]
@@ -157,4 +199,16 @@ pub struct PalletError {
     pub error: String,
     /// The error description.
     pub description: Vec<String>,
+    /// The index of the pallet in the runtime.
+    pub pallet_index: u8,
+    /// The index of the error in the error enum of the pallet.
+    pub error_index: u8,
+}
+
+impl PalletError {
+    /// Decode the error into the outer error enum `E` of the runtime, as generated by the
+    /// `subxt` macro, so that it can be matched against the typed errors of the pallets.
+    pub fn as_runtime_error<E: Decode>(&self) -> Result<E, codec::Error> {
+        E::decode(&mut &[self.pallet_index, self.error_index][..])
+    }
 }
@@ -30,6 +30,7 @@ use crate::{
     },
     Config,
     Error,
+    Event,
     Metadata,
     Phase,
     RuntimeError,
@@ -53,6 +54,23 @@ pub struct RawEvent {
     pub variant_index: u8,
     /// The raw Event data
     pub data: Bytes,
+    /// The phase of the block in which the Event was emitted.
+    pub phase: Phase,
+}
+
+impl RawEvent {
+    /// Returns the index of the extrinsic in the block which emitted the Event, if any.
+    pub fn extrinsic_index(&self) -> Option<u32> {
+        self.phase.extrinsic_index()
+    }
+
+    /// Decode the event into the outer event enum `E` of the runtime, as generated by the
+    /// `subxt` macro, so that it can be matched against the typed events of the pallets.
+    pub fn as_runtime_event<E: Decode>(&self) -> Result<E, codec::Error> {
+        let mut bytes = vec![self.pallet_index, self.variant_index];
+        bytes.extend(&self.data.0);
+        E::decode(&mut &bytes[..])
+    }
 }
 
 /// Events decoder.
@@ -74,6 +92,18 @@ where
         }
     }
 
+    /// Creates a new `EventsDecoder` from SCALE encoded metadata, e.g. to decode archived
+    /// events offline.
+    pub fn from_metadata_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::new(Metadata::from_bytes(bytes)?))
+    }
+
+    /// Decode a raw event into the outer event enum of the runtime, e.g. the `Event` enum
+    /// generated by the `subxt` macro, as [`RawEvent::as_runtime_event`] does.
+    pub fn decode_event<E: Decode>(&self, raw: &RawEvent) -> Result<E, Error> {
+        Ok(raw.as_runtime_event()?)
+    }
+
     /// Decode events.
     pub fn decode_events(&self, input: &mut &[u8]) -> Result<Vec<(Phase, Raw)>, Error> {
         let compact_len = <Compact<u32>>::decode(input)?;
@@ -82,58 +112,80 @@ where
 
         let mut r = Vec::new();
         for _ in 0..len {
-            // decode EventRecord
-            let phase = Phase::decode(input)?;
-            let pallet_index = input.read_byte()?;
-            let variant_index = input.read_byte()?;
-            log::debug!(
-                "phase {:?}, pallet_index {}, event_variant: {}",
-                phase,
-                pallet_index,
-                variant_index
-            );
-            log::debug!("remaining input: {}", hex::encode(&input));
-
-            let event_metadata = self.metadata.event(pallet_index, variant_index)?;
-
-            let mut event_data = Vec::<u8>::new();
-            let mut event_errors = Vec::<RuntimeError>::new();
-            let result = self.decode_raw_event(
-                &event_metadata,
-                input,
-                &mut event_data,
-                &mut event_errors,
-            );
-            let raw = match result {
-                Ok(()) => {
-                    log::debug!("raw bytes: {}", hex::encode(&event_data),);
-
-                    let event = RawEvent {
-                        pallet: event_metadata.pallet().to_string(),
-                        pallet_index,
-                        variant: event_metadata.event().to_string(),
-                        variant_index,
-                        data: event_data.into(),
-                    };
-
-                    // topics come after the event data in EventRecord
-                    let topics = Vec::<T::Hash>::decode(input)?;
-                    log::debug!("topics: {:?}", topics);
-
-                    Raw::Event(event)
-                }
-                Err(err) => return Err(err),
-            };
+            self.decode_event_record(input, &mut r)?;
+        }
+        Ok(r)
+    }
 
-            if event_errors.is_empty() {
-                r.push((phase.clone(), raw));
-            }
+    /// Decode events lazily, one event record at a time, without decoding all events of the
+    /// block up front.
+    ///
+    /// Only the length prefix of the events is decoded by this call.
+    pub fn decode_events_iter<'a>(
+        &'a self,
+        mut input: &'a [u8],
+    ) -> Result<EventsIter<'a, T>, Error> {
+        let compact_len = <Compact<u32>>::decode(&mut input)?;
+        log::debug!("decoding {} events lazily", compact_len.0);
+        Ok(EventsIter {
+            decoder: self,
+            input,
+            remaining: compact_len.0,
+            pending: Vec::new().into_iter(),
+        })
+    }
 
-            for err in event_errors {
-                r.push((phase.clone(), Raw::Error(err)));
-            }
+    /// Decode a single `EventRecord`, pushing the event, or the errors of a failed
+    /// extrinsic, to `output`.
+    fn decode_event_record(
+        &self,
+        input: &mut &[u8],
+        output: &mut Vec<(Phase, Raw)>,
+    ) -> Result<(), Error> {
+        let phase = Phase::decode(input)?;
+        let pallet_index = input.read_byte()?;
+        let variant_index = input.read_byte()?;
+        log::debug!(
+            "phase {:?}, pallet_index {}, event_variant: {}",
+            phase,
+            pallet_index,
+            variant_index
+        );
+        log::debug!("remaining input: {}", hex::encode(&input));
+
+        let event_metadata = self.metadata.event(pallet_index, variant_index)?;
+
+        let mut event_data = Vec::<u8>::new();
+        let mut event_errors = Vec::<RuntimeError>::new();
+        self.decode_raw_event(
+            &event_metadata,
+            input,
+            &mut event_data,
+            &mut event_errors,
+        )?;
+        log::debug!("raw bytes: {}", hex::encode(&event_data),);
+
+        let event = RawEvent {
+            pallet: event_metadata.pallet().to_string(),
+            pallet_index,
+            variant: event_metadata.event().to_string(),
+            variant_index,
+            data: event_data.into(),
+            phase: phase.clone(),
+        };
+
+        // topics come after the event data in EventRecord
+        let topics = Vec::<T::Hash>::decode(input)?;
+        log::debug!("topics: {:?}", topics);
+
+        if event_errors.is_empty() {
+            output.push((phase.clone(), Raw::Event(event)));
         }
-        Ok(r)
+
+        for err in event_errors {
+            output.push((phase.clone(), Raw::Error(err)));
+        }
+        Ok(())
     }
 
     fn decode_raw_event(
@@ -333,6 +385,41 @@ where
     }
 }
 
+/// Iterator over the events of a block, decoding one event record at a time.
+///
+/// Created by [`EventsDecoder::decode_events_iter`]. Iteration ends after the first error.
+pub struct EventsIter<'a, T> {
+    decoder: &'a EventsDecoder<T>,
+    input: &'a [u8],
+    remaining: u32,
+    pending: std::vec::IntoIter<(Phase, Raw)>,
+}
+
+impl<'a, T: Config> Iterator for EventsIter<'a, T> {
+    type Item = Result<EventRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((phase, event)) = self.pending.next() {
+                return Some(Ok(EventRecord { phase, event }))
+            }
+            if self.remaining == 0 {
+                return None
+            }
+            self.remaining -= 1;
+            let mut records = Vec::new();
+            if let Err(err) = self
+                .decoder
+                .decode_event_record(&mut self.input, &mut records)
+            {
+                self.remaining = 0;
+                return Some(Err(err))
+            }
+            self.pending = records.into_iter();
+        }
+    }
+}
+
 /// Raw event or error event
 #[derive(Debug)]
 pub enum Raw {
@@ -342,6 +429,36 @@ pub enum Raw {
     Error(RuntimeError),
 }
 
+/// A decoded event of a block, along with the phase of the block in which it was emitted.
+#[derive(Debug)]
+pub struct EventRecord {
+    /// The phase of the block in which the event was emitted.
+    pub phase: Phase,
+    /// The event, or the error of a failed extrinsic.
+    pub event: Raw,
+}
+
+impl EventRecord {
+    /// Returns the index of the extrinsic in the block which emitted the event, if any.
+    pub fn extrinsic_index(&self) -> Option<u32> {
+        self.phase.extrinsic_index()
+    }
+
+    /// Attempts to decode the event data into the supplied type.
+    ///
+    /// Returns `None` if the event is a different event or an error.
+    pub fn as_event<E: Event>(&self) -> Result<Option<E>, codec::Error> {
+        match &self.event {
+            Raw::Event(event)
+                if event.pallet == E::PALLET && event.variant == E::EVENT =>
+            {
+                Ok(Some(E::decode(&mut &event.data[..])?))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum EventsDecodingError {
     /// Unsupported primitive type
@@ -0,0 +1,125 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Account and signature types for chains with Ethereum-style accounts, e.g. Moonbeam.
+//!
+//! Copied from Moonbeam's `account` crate to avoid a dependency.
+
+use crate::Error;
+use codec::{
+    Decode,
+    Encode,
+};
+use sp_core::{
+    ecdsa,
+    keccak_256,
+};
+use sp_runtime::traits::{
+    IdentifyAccount,
+    Lazy,
+    Verify,
+};
+
+/// A 20 byte Ethereum-style account id, derived from the keccak-256 hash of an ecdsa public
+/// key.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Encode,
+    Decode,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct AccountId20(pub [u8; 20]);
+
+impl std::fmt::Display for AccountId20 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl From<[u8; 20]> for AccountId20 {
+    fn from(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl TryFrom<ecdsa::Public> for AccountId20 {
+    type Error = Error;
+
+    /// Derive the account of the public key, failing if the key is not a valid compressed
+    /// secp256k1 key, as can be the case for keys created with `ecdsa::Public::from_raw`.
+    fn try_from(public: ecdsa::Public) -> Result<Self, Error> {
+        let public = libsecp256k1::PublicKey::parse_compressed(&public.0)
+            .map_err(|err| format!("Invalid ecdsa public key: {:?}", err))?;
+        // the account is the last 20 bytes of the hash of the uncompressed key, without
+        // the leading tag byte
+        let hash = keccak_256(&public.serialize()[1..]);
+        let mut account = [0u8; 20];
+        account.copy_from_slice(&hash[12..]);
+        Ok(Self(account))
+    }
+}
+
+/// An ecdsa signature over the keccak-256 hash of a payload.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct EthereumSignature(pub ecdsa::Signature);
+
+impl From<ecdsa::Signature> for EthereumSignature {
+    fn from(signature: ecdsa::Signature) -> Self {
+        Self(signature)
+    }
+}
+
+impl Verify for EthereumSignature {
+    type Signer = EthereumSigner;
+
+    fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId20) -> bool {
+        match self.0.recover_prehashed(&keccak_256(msg.get())) {
+            Some(public) => {
+                matches!(AccountId20::try_from(public), Ok(account) if &account == signer)
+            }
+            None => false,
+        }
+    }
+}
+
+/// The public key of an [`EthereumSignature`], identifying an [`AccountId20`].
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct EthereumSigner(AccountId20);
+
+impl TryFrom<ecdsa::Public> for EthereumSigner {
+    type Error = Error;
+
+    fn try_from(public: ecdsa::Public) -> Result<Self, Error> {
+        Ok(Self(public.try_into()?))
+    }
+}
+
+impl IdentifyAccount for EthereumSigner {
+    type AccountId = AccountId20;
+
+    fn into_account(self) -> AccountId20 {
+        self.0
+    }
+}
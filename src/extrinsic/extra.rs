@@ -136,15 +136,15 @@ where
 ///
 /// # Note
 ///
-/// This is modified from the substrate version to allow passing in of the genesis hash, which is
-/// returned via `additional_signed()`. It assumes therefore `Era::Immortal` (The transaction is
-/// valid forever)
+/// This is modified from the substrate version to allow passing in of the hash of the block
+/// from which the era is counted (the genesis hash for `Era::Immortal`), which is returned via
+/// `additional_signed()`.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct CheckMortality<T: Config>(
     /// The default structure for the Extra encoding
     pub (Era, PhantomData<T>),
-    /// Local genesis hash to be used for `AdditionalSigned`
+    /// Local era checkpoint block hash to be used for `AdditionalSigned`
     #[codec(skip)]
     pub T::Hash,
 );
@@ -226,17 +226,46 @@ impl SignedExtension for ChargeTransactionPayment {
     }
 }
 
+/// Parameters for the transaction extras which are chosen by the user rather than fetched
+/// from the chain.
+#[derive(Clone, Debug)]
+pub struct ExtrinsicParams<T: Config> {
+    /// The period for which the transaction is valid.
+    pub era: Era,
+    /// The hash of the block from which the `era` is counted. Must be set for a mortal era,
+    /// defaults to the genesis hash otherwise.
+    pub era_checkpoint: Option<T::Hash>,
+    /// The tip paid to the block author to increase the priority of the transaction.
+    pub tip: u128,
+}
+
+impl<T: Config> Default for ExtrinsicParams<T> {
+    fn default() -> Self {
+        Self {
+            era: Era::Immortal,
+            era_checkpoint: None,
+            tip: 0,
+        }
+    }
+}
+
 /// Trait for implementing transaction extras for a runtime.
 pub trait SignedExtra<T: Config>: SignedExtension {
     /// The type the extras.
     type Extra: SignedExtension + Send + Sync;
 
     /// Creates a new `SignedExtra`.
+    ///
+    /// The client passes the versions of the runtime and the genesis hash it knows, to include
+    /// in the `additional_signed` data of extensions like [`CheckGenesis`], along with the
+    /// nonce of the signer and the chosen params, e.g. the checkpoint of a mortal era for
+    /// [`CheckMortality`].
     fn new(
         spec_version: u32,
         tx_version: u32,
         nonce: T::Index,
         genesis_hash: T::Hash,
+        params: ExtrinsicParams<T>,
     ) -> Self;
 
     /// Returns the transaction extra.
@@ -251,6 +280,9 @@ pub struct DefaultExtra<T: Config> {
     tx_version: u32,
     nonce: T::Index,
     genesis_hash: T::Hash,
+    era: Era,
+    era_checkpoint: T::Hash,
+    tip: u128,
 }
 
 impl<T: Config + Clone + Debug + Eq + Send + Sync> SignedExtra<T> for DefaultExtra<T> {
@@ -269,12 +301,16 @@ impl<T: Config + Clone + Debug + Eq + Send + Sync> SignedExtra<T> for DefaultExt
         tx_version: u32,
         nonce: T::Index,
         genesis_hash: T::Hash,
+        params: ExtrinsicParams<T>,
     ) -> Self {
         DefaultExtra {
             spec_version,
             tx_version,
             nonce,
             genesis_hash,
+            era: params.era,
+            era_checkpoint: params.era_checkpoint.unwrap_or(genesis_hash),
+            tip: params.tip,
         }
     }
 
@@ -283,14 +319,23 @@ impl<T: Config + Clone + Debug + Eq + Send + Sync> SignedExtra<T> for DefaultExt
             CheckSpecVersion(PhantomData, self.spec_version),
             CheckTxVersion(PhantomData, self.tx_version),
             CheckGenesis(PhantomData, self.genesis_hash),
-            CheckMortality((Era::Immortal, PhantomData), self.genesis_hash),
+            CheckMortality((self.era, PhantomData), self.era_checkpoint),
             CheckNonce(self.nonce),
             CheckWeight(PhantomData),
-            ChargeTransactionPayment(u128::default()),
+            ChargeTransactionPayment(self.tip),
         )
     }
 }
 
+impl<T: Config + Clone + Debug + Eq + Send + Sync> DefaultExtra<T> {
+    /// Returns the nonce in the signed extensions of an extrinsic, e.g. of one decoded by
+    /// [`crate::Client::block_extrinsics`].
+    pub fn nonce_of(extra: &<Self as SignedExtra<T>>::Extra) -> T::Index {
+        let (_, _, _, _, check_nonce, _, _) = extra;
+        check_nonce.0
+    }
+}
+
 impl<T: Config + Clone + Debug + Eq + Send + Sync> SignedExtension for DefaultExtra<T> {
     const IDENTIFIER: &'static str = "DefaultExtra";
     type AccountId = T::AccountId;
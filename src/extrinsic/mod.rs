@@ -16,10 +16,16 @@
 
 //! Create signed or unsigned extrinsics.
 
+mod ethereum;
 mod extra;
 mod signer;
 
 pub use self::{
+    ethereum::{
+        AccountId20,
+        EthereumSignature,
+        EthereumSigner,
+    },
     extra::{
         ChargeTransactionPayment,
         CheckGenesis,
@@ -29,22 +35,33 @@ pub use self::{
         CheckTxVersion,
         CheckWeight,
         DefaultExtra,
+        ExtrinsicParams,
         SignedExtra,
     },
     signer::{
+        EcdsaSigner,
         PairSigner,
         Signer,
     },
 };
 
-use sp_runtime::traits::SignedExtension;
+use codec::{
+    Decode,
+    Encode,
+};
+use sp_runtime::traits::{
+    Hash,
+    SignedExtension,
+};
 use sp_version::RuntimeVersion;
 
 use crate::{
+    Call,
     Config,
     Encoded,
     Error,
     ExtrinsicExtraData,
+    Metadata,
 };
 
 /// UncheckedExtrinsic type.
@@ -55,6 +72,51 @@ pub type UncheckedExtrinsic<T> = sp_runtime::generic::UncheckedExtrinsic<
     <<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra,
 >;
 
+/// Encoding and hashing of a signed extrinsic, e.g. to track it before it is submitted.
+pub trait SignedExtrinsic<T: Config> {
+    /// Returns the SCALE encoded extrinsic, as submitted to the node.
+    fn encoded(&self) -> Encoded;
+
+    /// Returns the hash of the encoded extrinsic, as returned by the node on submission.
+    fn hash(&self) -> T::Hash;
+}
+
+impl<T> SignedExtrinsic<T> for UncheckedExtrinsic<T>
+where
+    T: Config + ExtrinsicExtraData<T>,
+{
+    fn encoded(&self) -> Encoded {
+        Encoded(self.encode())
+    }
+
+    fn hash(&self) -> T::Hash {
+        T::Hashing::hash_of(self)
+    }
+}
+
+/// An extrinsic of a block, with its call decoded into the outer call enum `C` of the runtime.
+pub type DecodedExtrinsic<T, C> = sp_runtime::generic::UncheckedExtrinsic<
+    <T as Config>::Address,
+    C,
+    <T as Config>::Signature,
+    <<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra,
+>;
+
+/// Decodes an extrinsic of a block, e.g. as returned by [`crate::rpc::Rpc::block`], along with
+/// its call into the outer call enum `C`, e.g. the `Call` enum generated by the `subxt` macro.
+pub fn decode_extrinsic<T, C>(
+    extrinsic: &T::Extrinsic,
+) -> Result<DecodedExtrinsic<T, C>, Error>
+where
+    T: Config + ExtrinsicExtraData<T>,
+    T::Signature: Decode,
+    C: Decode,
+{
+    // opaque extrinsics encode as the encoded extrinsic
+    let extrinsic = DecodedExtrinsic::<T, C>::decode(&mut &extrinsic.encode()[..])?;
+    Ok(extrinsic)
+}
+
 /// SignedPayload type.
 pub type SignedPayload<T> = sp_runtime::generic::SignedPayload<
     Encoded,
@@ -68,6 +130,7 @@ pub async fn create_signed<T>(
     nonce: T::Index,
     call: Encoded,
     signer: &(dyn Signer<T> + Send + Sync),
+    params: ExtrinsicParams<T>,
 ) -> Result<UncheckedExtrinsic<T>, Error>
 where
     T: Config + ExtrinsicExtraData<T>,
@@ -81,6 +144,52 @@ where
         tx_version,
         nonce,
         genesis_hash,
+        params,
+    );
+    let payload = SignedPayload::<T>::new(call, extra.extra())?;
+    let signed = signer.sign(payload).await?;
+    Ok(signed)
+}
+
+/// The chain parameters required to create a signed extrinsic without a connection to a node.
+#[derive(Clone, Debug)]
+pub struct OfflineParams<T: Config> {
+    /// The runtime spec version.
+    pub spec_version: u32,
+    /// The runtime transaction version.
+    pub tx_version: u32,
+    /// The genesis hash of the chain.
+    pub genesis_hash: T::Hash,
+    /// The nonce of the signing account.
+    pub nonce: T::Index,
+    /// Parameters for the transaction extras, e.g. the tip.
+    pub extrinsic_params: ExtrinsicParams<T>,
+}
+
+/// Creates a signed extrinsic without a connection to a node, using the given metadata to
+/// encode the call.
+///
+/// The encoded extrinsic can then be submitted at a later time e.g. with
+/// [`crate::rpc::Rpc::submit_extrinsic`].
+pub async fn create_signed_offline<T, C>(
+    metadata: &Metadata,
+    call: &C,
+    signer: &(dyn Signer<T> + Send + Sync),
+    params: OfflineParams<T>,
+) -> Result<UncheckedExtrinsic<T>, Error>
+where
+    T: Config + ExtrinsicExtraData<T>,
+    C: Call,
+    <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+        Send + Sync,
+{
+    let call = metadata.pallet(C::PALLET)?.encode_call(call)?;
+    let extra = <T as ExtrinsicExtraData<T>>::Extra::new(
+        params.spec_version,
+        params.tx_version,
+        params.nonce,
+        params.genesis_hash,
+        params.extrinsic_params,
     );
     let payload = SignedPayload::<T>::new(call, extra.extra())?;
     let signed = signer.sign(payload).await?;
@@ -23,11 +23,16 @@ use super::{
 };
 use crate::{
     Config,
+    Error,
     ExtrinsicExtraData,
     SignedExtra,
 };
 use codec::Encode;
-use sp_core::Pair;
+use sp_core::{
+    ecdsa,
+    keccak_256,
+    Pair,
+};
 use sp_runtime::traits::{
     IdentifyAccount,
     SignedExtension,
@@ -54,6 +59,10 @@ pub trait Signer<T: Config + ExtrinsicExtraData<T>> {
 }
 
 /// Extrinsic signer using a private key.
+///
+/// Signs with any `Pair` whose signature converts into the signature of the chain, e.g. an
+/// [`sp_core::sr25519::Pair`], [`sp_core::ed25519::Pair`] or [`ecdsa::Pair`] for chains with
+/// [`sp_runtime::MultiSignature`].
 #[derive(Clone, Debug)]
 pub struct PairSigner<T: Config, P: Pair> {
     account_id: T::AccountId,
@@ -80,6 +89,22 @@ where
         }
     }
 
+    /// Creates a new `Signer` from the raw 32 byte seed of a `Pair`.
+    pub fn from_seed(seed: &[u8; 32]) -> Self
+    where
+        P: Pair<Seed = [u8; 32]>,
+    {
+        Self::new(P::from_seed(seed))
+    }
+
+    /// Creates a new `Signer` from a mnemonic phrase and an optional password.
+    ///
+    /// The phrase may be followed by a derivation path, e.g. `//Alice`, as accepted by
+    /// [`Pair::from_string`].
+    pub fn from_phrase(phrase: &str, password: Option<&str>) -> Result<Self, Error> {
+        Ok(Self::new(P::from_string(phrase, password)?))
+    }
+
     /// Sets the nonce to a new value.
     pub fn set_nonce(&mut self, nonce: T::Index) {
         self.nonce = Some(nonce);
@@ -128,3 +153,83 @@ where
         Ok(extrinsic)
     }
 }
+
+/// Extrinsic signer using an ecdsa private key, for chains with Ethereum-style accounts which
+/// expect the keccak-256 hash of the signed payload to be signed, e.g. Moonbeam.
+///
+/// Use [`PairSigner`] with an [`ecdsa::Pair`] for chains verifying ecdsa signatures as
+/// [`sp_runtime::MultiSignature::Ecdsa`].
+#[derive(Clone)]
+pub struct EcdsaSigner<T: Config> {
+    account_id: T::AccountId,
+    nonce: Option<T::Index>,
+    signer: ecdsa::Pair,
+}
+
+impl<T> EcdsaSigner<T>
+where
+    T: Config,
+    <T::Signature as Verify>::Signer:
+        TryFrom<ecdsa::Public, Error = Error> + IdentifyAccount<AccountId = T::AccountId>,
+{
+    /// Creates a new `Signer` from an ecdsa `Pair`.
+    ///
+    /// Returns an error if the account id can't be derived from the public key of the pair.
+    pub fn new(signer: ecdsa::Pair) -> Result<Self, Error> {
+        let account_id =
+            <T::Signature as Verify>::Signer::try_from(signer.public())?.into_account();
+        Ok(Self {
+            account_id,
+            nonce: None,
+            signer,
+        })
+    }
+
+    /// Sets the nonce to a new value.
+    pub fn set_nonce(&mut self, nonce: T::Index) {
+        self.nonce = Some(nonce);
+    }
+
+    /// Increment the nonce.
+    pub fn increment_nonce(&mut self) {
+        self.nonce = self.nonce.map(|nonce| nonce + 1u32.into());
+    }
+
+    /// Returns the signer.
+    pub fn signer(&self) -> &ecdsa::Pair {
+        &self.signer
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> Signer<T> for EcdsaSigner<T>
+where
+    T: Config + ExtrinsicExtraData<T>,
+    T::AccountId: Into<T::Address> + 'static,
+    T::Signature: From<ecdsa::Signature>,
+    <<<T as ExtrinsicExtraData<T>>::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned: Send + Sync + 'static,
+{
+    fn account_id(&self) -> &T::AccountId {
+        &self.account_id
+    }
+
+    fn nonce(&self) -> Option<T::Index> {
+        self.nonce
+    }
+
+    async fn sign(
+        &self,
+        extrinsic: SignedPayload<T>,
+    ) -> Result<UncheckedExtrinsic<T>, String> {
+        let signature = extrinsic
+            .using_encoded(|payload| self.signer.sign_prehashed(&keccak_256(payload)));
+        let (call, extra, _) = extrinsic.deconstruct();
+        let extrinsic = UncheckedExtrinsic::<T>::new_signed(
+            call,
+            self.account_id.clone().into(),
+            signature.into(),
+            extra,
+        );
+        Ok(extrinsic)
+    }
+}
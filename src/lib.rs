@@ -40,6 +40,11 @@
 )]
 #![allow(clippy::type_complexity)]
 
+#[cfg(not(any(feature = "native", feature = "wasm")))]
+compile_error!(
+    "subxt requires either the `native` or the `wasm` feature for its rpc clients"
+);
+
 pub use frame_metadata::StorageHasher;
 pub use subxt_macro::subxt;
 
@@ -59,6 +64,8 @@ use core::{
     marker::PhantomData,
 };
 
+mod address;
+mod balance;
 mod client;
 mod config;
 mod error;
@@ -70,13 +77,20 @@ pub mod storage;
 mod subscription;
 
 pub use crate::{
+    address::{
+        AccountIndex,
+        Address20,
+        Address32,
+        RawAddress,
+    },
+    balance::AccountBalance,
     client::{
         Client,
         ClientBuilder,
         SubmittableExtrinsic,
+        TransactionManager,
     },
     config::{
-        AccountData,
         Config,
         ExtrinsicExtraData,
     },
@@ -84,36 +98,62 @@ pub use crate::{
         Error,
         PalletError,
         RuntimeError,
+        TransactionError,
     },
     events::{
+        EventRecord,
         EventsDecoder,
+        EventsIter,
+        Raw,
         RawEvent,
     },
     extrinsic::{
+        decode_extrinsic,
+        DecodedExtrinsic,
         DefaultExtra,
+        ExtrinsicParams,
         PairSigner,
         SignedExtra,
+        SignedExtrinsic,
         Signer,
         UncheckedExtrinsic,
     },
     metadata::Metadata,
     rpc::{
+        BatchInterrupted,
         BlockNumber,
+        DispatchClass,
+        DispatchInfo,
         ExtrinsicSuccess,
+        Health,
+        ListOrValue,
+        Pays,
         ReadProof,
+        ReconnectPolicy,
         RpcClient,
+        RpcTransport,
+        RuntimeDispatchInfo,
+        SyncState,
         SystemProperties,
+        TransactionStatus,
+        TransactionStatusStream,
+        Weight,
     },
     storage::{
         KeyIter,
         StorageEntry,
         StorageEntryKey,
         StorageMapKey,
+        VecDecoder,
     },
     subscription::{
+        EventFilter,
         EventStorageSubscription,
         EventSubscription,
+        FilteredEventSubscription,
         FinalizedEventStorageSubscription,
+        RuntimeEventSubscription,
+        StorageChangeSetSubscription,
     },
 };
 
@@ -123,6 +163,8 @@ pub trait Call: Encode {
     const PALLET: &'static str;
     /// Function name.
     const FUNCTION: &'static str;
+    /// Hash of the arguments of the call in the metadata the call was generated from.
+    const CALL_HASH: [u8; 32];
 
     /// Returns true if the given pallet and function names match this call.
     fn is_call(pallet: &str, function: &str) -> bool {
@@ -143,6 +185,15 @@ pub trait Event: Decode {
     }
 }
 
+/// Returns the hash of the preimage of a call, i.e. of its encoding with the hasher of the
+/// runtime, as expected by governance pallets.
+///
+/// For example to `propose` the outer runtime `Call` to the Democracy pallet, after noting
+/// its encoding with `note_preimage`.
+pub fn preimage_hash<T: Config, C: Encode>(call: &C) -> T::Hash {
+    <T::Hashing as sp_runtime::traits::Hash>::hash_of(call)
+}
+
 /// Wraps an already encoded byte vector, prevents being encoded as a raw byte vector as part of
 /// the transaction payload
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -155,7 +206,7 @@ impl codec::Encode for Encoded {
 }
 
 /// A phase of a block's execution.
-#[derive(Clone, Debug, Eq, PartialEq, Decode)]
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
 pub enum Phase {
     /// Applying an extrinsic.
     ApplyExtrinsic(u32),
@@ -165,6 +216,16 @@ pub enum Phase {
     Initialization,
 }
 
+impl Phase {
+    /// Returns the index of the extrinsic being applied, if any.
+    pub fn extrinsic_index(&self) -> Option<u32> {
+        match self {
+            Phase::ApplyExtrinsic(index) => Some(*index),
+            Phase::Finalization | Phase::Initialization => None,
+        }
+    }
+}
+
 /// A wrapper for any type `T` which implement encode/decode in a way compatible with `Vec<u8>`.
 ///
 /// This type is similar to [`WrapperOpaque`], but it differs in the way it stores the type `T`.
@@ -17,9 +17,13 @@
 use std::{
     collections::HashMap,
     convert::TryFrom,
+    sync::Arc,
 };
 
-use codec::Error as CodecError;
+use codec::{
+    Decode,
+    Error as CodecError,
+};
 
 use frame_metadata::{
     PalletConstantMetadata,
@@ -36,6 +40,7 @@ use crate::{
 };
 use scale_info::{
     form::PortableForm,
+    PortableRegistry,
     Type,
     Variant,
 };
@@ -55,6 +60,9 @@ pub enum MetadataError {
     /// Event is not in metadata.
     #[error("Pallet {0}, Event {0} not found")]
     EventNotFound(u8, u8),
+    /// Event with the given pallet and event name is not in metadata.
+    #[error("Event {0}::{1} not found")]
+    EventNameNotFound(&'static str, &'static str),
     /// Event is not in metadata.
     #[error("Pallet {0}, Error {0} not found")]
     ErrorNotFound(u8, u8),
@@ -78,8 +86,15 @@ pub enum MetadataError {
 }
 
 /// Runtime metadata.
+///
+/// Cheap to clone, since the decoded metadata is shared between the clones.
 #[derive(Clone, Debug)]
 pub struct Metadata {
+    inner: Arc<MetadataInner>,
+}
+
+#[derive(Debug)]
+struct MetadataInner {
     metadata: RuntimeMetadataLastVersion,
     pallets: HashMap<String, PalletMetadata>,
     events: HashMap<(u8, u8), EventMetadata>,
@@ -87,9 +102,17 @@ pub struct Metadata {
 }
 
 impl Metadata {
+    /// Decode the SCALE encoded metadata as returned by `state_getMetadata`, e.g. to work with
+    /// metadata archived without a connection to the node.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+        let meta: RuntimeMetadataPrefixed = Decode::decode(&mut &bytes[..])?;
+        Ok(meta.try_into()?)
+    }
+
     /// Returns a reference to [`PalletMetadata`].
     pub fn pallet(&self, name: &'static str) -> Result<&PalletMetadata, MetadataError> {
-        self.pallets
+        self.inner
+            .pallets
             .get(name)
             .ok_or(MetadataError::PalletNotFound(name.to_string()))
     }
@@ -101,12 +124,26 @@ impl Metadata {
         event_index: u8,
     ) -> Result<&EventMetadata, MetadataError> {
         let event = self
+            .inner
             .events
             .get(&(pallet_index, event_index))
             .ok_or(MetadataError::EventNotFound(pallet_index, event_index))?;
         Ok(event)
     }
 
+    /// Returns the metadata for the event with the given pallet and event names.
+    pub fn event_by_name(
+        &self,
+        pallet: &'static str,
+        event: &'static str,
+    ) -> Result<&EventMetadata, MetadataError> {
+        self.inner
+            .events
+            .values()
+            .find(|metadata| metadata.pallet == pallet && metadata.event == event)
+            .ok_or(MetadataError::EventNameNotFound(pallet, event))
+    }
+
     /// Returns the metadata for the error at the given pallet and error indices.
     pub fn error(
         &self,
@@ -114,20 +151,36 @@ impl Metadata {
         error_index: u8,
     ) -> Result<&ErrorMetadata, MetadataError> {
         let error = self
+            .inner
             .errors
             .get(&(pallet_index, error_index))
             .ok_or(MetadataError::ErrorNotFound(pallet_index, error_index))?;
         Ok(error)
     }
 
+    /// Returns the hash of the arguments of the call in this metadata, to compare with
+    /// [`Call::CALL_HASH`].
+    pub fn call_hash<C: Call>(&self) -> Result<[u8; 32], MetadataError> {
+        let call = self.pallet(C::PALLET)?.call(C::FUNCTION)?;
+        Ok(subxt_metadata::get_call_hash(
+            &self.inner.metadata.types,
+            call.variant(),
+        ))
+    }
+
+    /// Returns the registry of all types of the runtime, e.g. to browse them.
+    pub fn types(&self) -> &PortableRegistry {
+        &self.inner.metadata.types
+    }
+
     /// Resolve a type definition.
     pub fn resolve_type(&self, id: u32) -> Option<&Type<PortableForm>> {
-        self.metadata.types.resolve(id)
+        self.inner.metadata.types.resolve(id)
     }
 
     /// Return the runtime metadata.
     pub fn runtime_metadata(&self) -> &RuntimeMetadataLastVersion {
-        &self.metadata
+        &self.inner.metadata
     }
 }
 
@@ -135,25 +188,48 @@ impl Metadata {
 pub struct PalletMetadata {
     index: u8,
     name: String,
-    calls: HashMap<String, u8>,
+    calls: HashMap<String, CallMetadata>,
+    storage_prefix: Option<String>,
     storage: HashMap<String, StorageEntryMetadata<PortableForm>>,
     constants: HashMap<String, PalletConstantMetadata<PortableForm>>,
 }
 
 impl PalletMetadata {
+    /// Get the index of the pallet in the runtime.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Get the name of the pallet.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get a call's metadata by name.
+    pub fn call(&self, name: &'static str) -> Result<&CallMetadata, MetadataError> {
+        self.calls
+            .get(name)
+            .ok_or(MetadataError::CallNotFound(name))
+    }
+
     pub fn encode_call<C>(&self, call: &C) -> Result<Encoded, MetadataError>
     where
         C: Call,
     {
-        let fn_index = self
-            .calls
-            .get(C::FUNCTION)
-            .ok_or(MetadataError::CallNotFound(C::FUNCTION))?;
-        let mut bytes = vec![self.index, *fn_index];
+        let fn_index = self.call(C::FUNCTION)?.index();
+        let mut bytes = vec![self.index, fn_index];
         bytes.extend(call.encode());
         Ok(Encoded(bytes))
     }
 
+    /// Get the prefix of the storage keys of the pallet, hashed into the first part of the
+    /// key of every storage entry of the pallet, or `None` if the pallet has no storage.
+    pub fn storage_prefix(&self) -> Option<&str> {
+        self.storage_prefix.as_deref()
+    }
+
+    /// Get a storage entry's metadata by name, including its hashers and its key and value
+    /// types.
     pub fn storage(
         &self,
         key: &'static str,
@@ -174,6 +250,34 @@ impl PalletMetadata {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct CallMetadata {
+    call: String,
+    variant: Variant<PortableForm>,
+}
+
+impl CallMetadata {
+    /// Get the name of the call.
+    pub fn call(&self) -> &str {
+        &self.call
+    }
+
+    /// Get the index of the call in the pallet.
+    pub fn index(&self) -> u8 {
+        self.variant.index()
+    }
+
+    /// Get the documentation of the call.
+    pub fn docs(&self) -> &[String] {
+        self.variant.docs()
+    }
+
+    /// Get the type def variant for the call.
+    pub fn variant(&self) -> &Variant<PortableForm> {
+        &self.variant
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct EventMetadata {
     pallet: String,
@@ -192,6 +296,11 @@ impl EventMetadata {
         &self.event
     }
 
+    /// Get the index of the event in the pallet.
+    pub fn index(&self) -> u8 {
+        self.variant.index()
+    }
+
     /// Get the type def variant for the pallet event.
     pub fn variant(&self) -> &Variant<PortableForm> {
         &self.variant
@@ -266,11 +375,21 @@ impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
                     let calls = type_def_variant
                         .variants()
                         .iter()
-                        .map(|v| (v.name().clone(), v.index()))
+                        .map(|v| {
+                            let call = CallMetadata {
+                                call: v.name().clone(),
+                                variant: v.clone(),
+                            };
+                            (v.name().clone(), call)
+                        })
                         .collect();
                     Ok(calls)
                 })?;
 
+                let storage_prefix = pallet
+                    .storage
+                    .as_ref()
+                    .map(|storage| storage.prefix.clone());
                 let storage = pallet.storage.as_ref().map_or(HashMap::new(), |storage| {
                     storage
                         .entries
@@ -289,6 +408,7 @@ impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
                     index: pallet.index,
                     name: pallet.name.to_string(),
                     calls,
+                    storage_prefix,
                     storage,
                     constants,
                 };
@@ -348,10 +468,12 @@ impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
             .collect();
 
         Ok(Self {
-            metadata,
-            pallets,
-            events,
-            errors,
+            inner: Arc::new(MetadataInner {
+                metadata,
+                pallets,
+                events,
+                errors,
+            }),
         })
     }
 }
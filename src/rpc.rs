@@ -21,18 +21,42 @@
 // Related: https://github.com/paritytech/subxt/issues/66
 #![allow(irrefutable_let_patterns)]
 
-use std::sync::Arc;
+#[cfg(feature = "wasm")]
+mod browser;
+
+#[cfg(feature = "wasm")]
+pub use browser::BrowserWsClient;
+
+#[cfg(feature = "native")]
+use std::sync::atomic::{
+    AtomicU32,
+    Ordering,
+};
+use std::{
+    sync::Arc,
+    time::Duration,
+};
 
 use codec::{
+    Compact,
     Decode,
     Encode,
     Error as CodecError,
 };
-use core::{
-    convert::TryInto,
-    marker::PhantomData,
+use core::marker::PhantomData;
+#[cfg(feature = "native")]
+use futures::lock::Mutex;
+use futures::{
+    stream::{
+        self,
+        BoxStream,
+    },
+    Stream,
+    StreamExt,
 };
-use frame_metadata::RuntimeMetadataPrefixed;
+#[cfg(feature = "native")]
+use futures_timer::Delay;
+#[cfg(feature = "native")]
 use jsonrpsee_http_client::{
     HttpClient,
     HttpClientBuilder,
@@ -48,10 +72,15 @@ use jsonrpsee_types::{
     JsonValue,
     Subscription,
 };
+#[cfg(feature = "native")]
 use jsonrpsee_ws_client::{
     WsClient,
     WsClientBuilder,
 };
+use scale_info::{
+    TypeDef,
+    TypeDefPrimitive,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -71,25 +100,35 @@ use sp_runtime::{
         SignedBlock,
     },
     traits::Hash,
+    ApplyExtrinsicResult,
+    DispatchError,
 };
 use sp_version::RuntimeVersion;
 
 use crate::{
-    error::Error,
+    error::{
+        Error,
+        TransactionError,
+    },
     events::{
         EventsDecoder,
+        Raw,
         RawEvent,
     },
+    metadata::MetadataError,
     storage::StorageKeyPrefix,
     subscription::{
         EventStorageSubscription,
         EventSubscription,
         FinalizedEventStorageSubscription,
+        StorageChangeSetSubscription,
         SystemEvents,
     },
     Config,
+    Encoded,
     Event,
     Metadata,
+    Phase,
 };
 
 /// A number type that can be serialized both as a number or a string that encodes a number in a
@@ -110,7 +149,7 @@ pub enum NumberOrHex {
 }
 
 /// RPC list or value wrapper.
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum ListOrValue<T> {
     /// A list of values of given type.
@@ -139,16 +178,245 @@ impl From<u32> for BlockNumber {
     }
 }
 
-/// System properties for a Substrate-based runtime
+/// System properties for a Substrate-based runtime, as returned by `system_properties`
+///
+/// The properties are set in the chain spec of the node, so each of them may be missing.
+/// Chains with several tokens give the decimals and symbols as lists, with the native token
+/// first.
 #[derive(serde::Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SystemProperties {
     /// The address format
-    pub ss58_format: u8,
-    /// The number of digits after the decimal point in the native token
-    pub token_decimals: u8,
-    /// The symbol of the native token
-    pub token_symbol: String,
+    pub ss58_format: Option<u16>,
+    /// The number of digits after the decimal point in the tokens
+    pub token_decimals: Option<ListOrValue<u8>>,
+    /// The symbols of the tokens
+    pub token_symbol: Option<ListOrValue<String>>,
+}
+
+/// Health of a node, as returned by `system_health`
+#[derive(serde::Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Health {
+    /// The number of connected peers
+    pub peers: usize,
+    /// Whether the node is syncing
+    pub is_syncing: bool,
+    /// Whether the node should be connected to peers, i.e. is not running a local chain
+    pub should_have_peers: bool,
+}
+
+/// Sync status of a node, as returned by `system_syncState`
+#[derive(serde::Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState {
+    /// The block number the node started syncing from
+    pub starting_block: u64,
+    /// The number of the best block of the node
+    pub current_block: u64,
+    /// The highest block number known to the node's peers, if any
+    pub highest_block: Option<u64>,
+}
+
+/// A generalized group of dispatch types.
+///
+/// # Note
+///
+/// This is copied from `frame-support` to avoid a dependency on that crate. Therefore it
+/// must be kept compatible with that type from the target substrate version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "camelCase")]
+pub enum DispatchClass {
+    /// A normal dispatch.
+    Normal,
+    /// An operational dispatch.
+    Operational,
+    /// A mandatory dispatch.
+    Mandatory,
+}
+
+/// Explicit enum to denote if a transaction pays fee or not.
+///
+/// # Note
+///
+/// This is copied from `frame-support` to avoid a dependency on that crate. Therefore it
+/// must be kept compatible with that type from the target substrate version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum Pays {
+    /// Transactor will pay related fees.
+    Yes,
+    /// Transactor will NOT pay related fees.
+    No,
+}
+
+/// The weight of a dispatch.
+///
+/// Runtimes since Weight v2 measure both the computation time and the size of the storage
+/// proof of a dispatch. The weight of older runtimes is a bare `u64` of the computation time,
+/// with a `proof_size` of zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(from = "WeightRepr")]
+pub struct Weight {
+    /// The computation time of the dispatch, in picoseconds.
+    pub ref_time: u64,
+    /// The size of the storage proof of the dispatch, in bytes.
+    pub proof_size: u64,
+}
+
+impl From<u64> for Weight {
+    fn from(ref_time: u64) -> Self {
+        Self {
+            ref_time,
+            proof_size: 0,
+        }
+    }
+}
+
+/// The representations of a [`Weight`] returned by the RPC of old and new runtimes.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WeightRepr {
+    V1(u64),
+    V2 {
+        #[serde(alias = "refTime")]
+        ref_time: u64,
+        #[serde(alias = "proofSize", default)]
+        proof_size: u64,
+    },
+}
+
+impl From<WeightRepr> for Weight {
+    fn from(repr: WeightRepr) -> Self {
+        match repr {
+            WeightRepr::V1(ref_time) => ref_time.into(),
+            WeightRepr::V2 {
+                ref_time,
+                proof_size,
+            } => {
+                Self {
+                    ref_time,
+                    proof_size,
+                }
+            }
+        }
+    }
+}
+
+/// The weight, class and fee payment of a dispatched extrinsic, as reported by the
+/// `System::ExtrinsicSuccess` event.
+///
+/// # Note
+///
+/// This mirrors the type of `frame-support` to avoid a dependency on that crate. The weight
+/// is decoded in the shape the metadata gives it, see
+/// [`decode_with_metadata`](Self::decode_with_metadata), but the class and fee payment
+/// must be kept compatible with that type from the target substrate version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchInfo {
+    /// Weight of this transaction.
+    pub weight: Weight,
+    /// Class of this transaction.
+    pub class: DispatchClass,
+    /// Does this transaction pay fees.
+    pub pays_fee: Pays,
+}
+
+impl DispatchInfo {
+    /// Decode the data of a `System::ExtrinsicSuccess` event, detecting from the type of the
+    /// weight in the metadata whether it is a bare `u64` or the `Weight` of Weight v2.
+    pub fn decode_with_metadata(
+        input: &mut &[u8],
+        metadata: &Metadata,
+    ) -> Result<Self, Error> {
+        let weight_type = dispatch_info_weight_type(metadata)?;
+        Ok(Self {
+            weight: decode_weight(metadata, weight_type, input)?,
+            class: Decode::decode(input)?,
+            pays_fee: Decode::decode(input)?,
+        })
+    }
+}
+
+/// Look up the type of the weight of the dispatch info in the `System::ExtrinsicSuccess`
+/// event.
+fn dispatch_info_weight_type(metadata: &Metadata) -> Result<u32, Error> {
+    let event = metadata.event_by_name("System", "ExtrinsicSuccess")?;
+    let dispatch_info = event
+        .variant()
+        .fields()
+        .first()
+        .ok_or("ExtrinsicSuccess event has no dispatch info")?
+        .ty()
+        .id();
+    let ty = metadata
+        .resolve_type(dispatch_info)
+        .ok_or(MetadataError::TypeNotFound(dispatch_info))?;
+    match ty.type_def() {
+        TypeDef::Composite(composite) => {
+            composite
+                .fields()
+                .iter()
+                .find(|field| field.name().map(String::as_str) == Some("weight"))
+                .map(|field| field.ty().id())
+                .ok_or_else(|| "Dispatch info has no weight".into())
+        }
+        _ => Err("Dispatch info is not a struct".into()),
+    }
+}
+
+/// Decode a weight of the given type, either a (compact) `u64` or a struct of them.
+fn decode_weight(
+    metadata: &Metadata,
+    type_id: u32,
+    input: &mut &[u8],
+) -> Result<Weight, Error> {
+    let ty = metadata
+        .resolve_type(type_id)
+        .ok_or(MetadataError::TypeNotFound(type_id))?;
+    match ty.type_def() {
+        TypeDef::Primitive(TypeDefPrimitive::U64) => Ok(u64::decode(input)?.into()),
+        TypeDef::Compact(_) => Ok(Compact::<u64>::decode(input)?.0.into()),
+        TypeDef::Composite(composite) => {
+            let mut weight = Weight::default();
+            for field in composite.fields() {
+                let value = decode_weight(metadata, field.ty().id(), input)?.ref_time;
+                match field.name().map(String::as_str) {
+                    Some("proof_size") => weight.proof_size = value,
+                    _ => weight.ref_time = value,
+                }
+            }
+            Ok(weight)
+        }
+        _ => Err(format!("Unsupported weight type {}", type_id).into()),
+    }
+}
+
+/// Information related to a dispatchable's class, weight, and fee, as returned by
+/// `payment_queryInfo`.
+///
+/// # Note
+///
+/// This is copied from `pallet-transaction-payment-rpc-runtime-api` to avoid a dependency on
+/// that crate. Therefore it must be kept compatible with that type from the target substrate
+/// version.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeDispatchInfo {
+    /// Weight of this dispatch.
+    pub weight: Weight,
+    /// Class of this dispatch.
+    pub class: DispatchClass,
+    /// The inclusion fee of this dispatch, not including any tip.
+    #[serde(deserialize_with = "deserialize_balance")]
+    pub partial_fee: u128,
+}
+
+fn deserialize_balance<'de, D>(deserializer: D) -> Result<u128, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let balance = String::deserialize(deserializer)?;
+    balance.parse().map_err(serde::de::Error::custom)
 }
 
 /// Possible transaction status events.
@@ -184,15 +452,59 @@ pub enum TransactionStatus<Hash, BlockHash> {
     Invalid,
 }
 
+impl<Hash, BlockHash> TransactionStatus<Hash, BlockHash> {
+    /// Returns `true` if no further status updates follow this one.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Self::Finalized(_)
+                | Self::FinalityTimeout(_)
+                | Self::Usurped(_)
+                | Self::Dropped
+                | Self::Invalid
+        )
+    }
+}
+
+/// Stream of the status updates of a submitted extrinsic, ending after a final status.
+pub type TransactionStatusStream<T> = BoxStream<
+    'static,
+    Result<TransactionStatus<<T as Config>::Hash, <T as Config>::Hash>, Error>,
+>;
+
+/// A custom transport for the JSON-RPC requests of a [`RpcClient`], e.g. to add
+/// authentication headers for a hosted endpoint, or to mock the node in tests.
+///
+/// Subscriptions are not supported on custom transports.
+#[async_trait::async_trait]
+pub trait RpcTransport: Send + Sync {
+    /// Send a JSON-RPC request, returning the result of the response.
+    async fn request(
+        &self,
+        method: &str,
+        params: Vec<JsonValue>,
+    ) -> Result<JsonValue, Error>;
+}
+
 /// Rpc client wrapper.
 /// This is workaround because adding generic types causes the macros to fail.
 #[derive(Clone)]
 pub enum RpcClient {
     /// JSONRPC client WebSocket transport.
+    #[cfg(feature = "native")]
     WebSocket(Arc<WsClient>),
+    /// JSONRPC client WebSocket transport, reconnecting when the connection is dropped.
+    #[cfg(feature = "native")]
+    ReconnectingWebSocket(Arc<ReconnectingWsClient>),
     /// JSONRPC client HTTP transport.
     // NOTE: Arc because `HttpClient` is not clone.
+    #[cfg(feature = "native")]
     Http(Arc<HttpClient>),
+    /// JSONRPC client using the WebSocket of the browser.
+    #[cfg(feature = "wasm")]
+    Browser(Arc<BrowserWsClient>),
+    /// JSONRPC client with a custom transport.
+    Custom(Arc<dyn RpcTransport>),
 }
 
 impl RpcClient {
@@ -201,6 +513,10 @@ impl RpcClient {
     /// Infers the protocol from the URL, supports:
     ///     - Websockets (`ws://`, `wss://`)
     ///     - Http (`http://`, `https://`)
+    ///
+    /// Without the `native` feature, only WebSocket URLs are supported, which are connected
+    /// to through the WebSocket of the browser.
+    #[cfg(feature = "native")]
     pub async fn try_from_url(url: &str) -> Result<Self, Error> {
         if url.starts_with("ws://") || url.starts_with("wss://") {
             let client = WsClientBuilder::default()
@@ -214,72 +530,420 @@ impl RpcClient {
         }
     }
 
+    /// Create a new [`RpcClient`] from the given URL.
+    ///
+    /// Infers the protocol from the URL, supports:
+    ///     - Websockets (`ws://`, `wss://`)
+    ///
+    /// Without the `native` feature, only WebSocket URLs are supported, which are connected
+    /// to through the WebSocket of the browser.
+    #[cfg(not(feature = "native"))]
+    pub async fn try_from_url(url: &str) -> Result<Self, Error> {
+        if url.starts_with("ws://") || url.starts_with("wss://") {
+            Self::try_browser_from_url(url).await
+        } else {
+            Err(format!(
+                "Unsupported rpc endpoint {}, only WebSocket endpoints are supported \
+                 without the `native` feature",
+                url
+            )
+            .into())
+        }
+    }
+
+    /// Create a new [`RpcClient`] connected to the given WebSocket URL through the
+    /// WebSocket of the browser.
+    #[cfg(feature = "wasm")]
+    pub async fn try_browser_from_url(url: &str) -> Result<Self, Error> {
+        let client = BrowserWsClient::connect(url).await?;
+        Ok(RpcClient::Browser(Arc::new(client)))
+    }
+
+    /// Create a new WebSocket [`RpcClient`] from the given URL, which reconnects to the node
+    /// according to the given policy when the connection is dropped.
+    pub async fn try_reconnecting_from_url(
+        url: &str,
+        policy: ReconnectPolicy,
+    ) -> Result<Self, Error> {
+        Self::try_reconnecting_from_urls(vec![url.to_owned()], policy).await
+    }
+
+    /// Create a new WebSocket [`RpcClient`] connected to the first reachable of the given
+    /// URLs, which fails over to the other URLs according to the given policy when the
+    /// connection is dropped.
+    pub async fn try_reconnecting_from_urls(
+        urls: Vec<String>,
+        policy: ReconnectPolicy,
+    ) -> Result<Self, Error> {
+        #[cfg(feature = "native")]
+        {
+            let client = ReconnectingWsClient::connect_any(urls, policy).await?;
+            Ok(RpcClient::ReconnectingWebSocket(Arc::new(client)))
+        }
+        #[cfg(not(feature = "native"))]
+        {
+            let _ = (urls, policy);
+            Err("Reconnecting clients require the `native` feature".into())
+        }
+    }
+
+    /// Create a new [`RpcClient`] sending its requests through the given transport.
+    pub fn from_transport<R: RpcTransport + 'static>(transport: R) -> Self {
+        RpcClient::Custom(Arc::new(transport))
+    }
+
+    /// Returns the URL of the endpoint a reconnecting client is currently connected to.
+    pub async fn active_url(&self) -> Option<String> {
+        match self {
+            #[cfg(feature = "native")]
+            Self::ReconnectingWebSocket(inner) => Some(inner.active_url().await),
+            _ => None,
+        }
+    }
+
     /// Start a JSON-RPC request.
     pub async fn request<'a, T: DeserializeOwned + std::fmt::Debug>(
         &self,
         method: &str,
         params: &[JsonValue],
     ) -> Result<T, Error> {
-        let params = params.into();
         log::debug!("request {}: {:?}", method, params);
         let data = match self {
+            #[cfg(feature = "native")]
             Self::WebSocket(inner) => {
-                inner.request(method, params).await.map_err(Into::into)
+                inner
+                    .request(method, params.into())
+                    .await
+                    .map_err(Into::into)
+            }
+            #[cfg(feature = "native")]
+            Self::ReconnectingWebSocket(inner) => inner.request(method, params).await,
+            #[cfg(feature = "native")]
+            Self::Http(inner) => {
+                inner
+                    .request(method, params.into())
+                    .await
+                    .map_err(Into::into)
+            }
+            #[cfg(feature = "wasm")]
+            Self::Browser(inner) => inner.request(method, params).await,
+            Self::Custom(inner) => {
+                let result = inner.request(method, params.to_vec()).await?;
+                serde_json::from_value(result).map_err(Into::into)
             }
-            Self::Http(inner) => inner.request(method, params).await.map_err(Into::into),
         };
         data
     }
 
+    /// Returns the number of times the connection to the node has been re-established.
+    ///
+    /// Subscriptions other than event and storage subscriptions are terminated when the
+    /// connection is dropped, so a change of this number signals that any state derived
+    /// from them should be fetched again.
+    pub fn reconnections(&self) -> u32 {
+        match self {
+            #[cfg(feature = "native")]
+            Self::ReconnectingWebSocket(inner) => inner.reconnections(),
+            _ => 0,
+        }
+    }
+
+    /// Returns `true` if the client re-establishes dropped connections.
+    pub fn is_reconnecting(&self) -> bool {
+        #[cfg(feature = "native")]
+        {
+            matches!(self, Self::ReconnectingWebSocket(_))
+        }
+        #[cfg(not(feature = "native"))]
+        {
+            false
+        }
+    }
+
     /// Start a JSON-RPC Subscription.
+    ///
+    /// Dropping the returned [`Subscription`] calls `unsubscribe_method` on the node, so
+    /// the node stops producing notifications for it.
     pub async fn subscribe<'a, T: DeserializeOwned>(
         &self,
         subscribe_method: &str,
         params: &[JsonValue],
         unsubscribe_method: &str,
     ) -> Result<Subscription<T>, Error> {
-        let params = params.into();
         match self {
+            #[cfg(feature = "native")]
             Self::WebSocket(inner) => {
                 inner
-                    .subscribe(subscribe_method, params, unsubscribe_method)
+                    .subscribe(subscribe_method, params.into(), unsubscribe_method)
                     .await
                     .map_err(Into::into)
             }
+            #[cfg(feature = "native")]
+            Self::ReconnectingWebSocket(inner) => {
+                inner
+                    .subscribe(subscribe_method, params, unsubscribe_method)
+                    .await
+            }
+            #[cfg(feature = "wasm")]
+            Self::Browser(inner) => {
+                inner
+                    .subscribe(subscribe_method, params, unsubscribe_method)
+                    .await
+            }
+            #[cfg(feature = "native")]
             Self::Http(_) => {
                 Err(RpcError::Custom(
                     "Subscriptions not supported on HTTP transport".to_owned(),
                 )
                 .into())
             }
+            Self::Custom(_) => {
+                Err(RpcError::Custom(
+                    "Subscriptions not supported on custom transports".to_owned(),
+                )
+                .into())
+            }
         }
     }
 }
 
+/// Policy for re-establishing a dropped WebSocket connection.
+#[derive(Clone, Debug)]
+pub struct ReconnectPolicy {
+    /// The maximum number of consecutive attempts to connect before giving up.
+    pub max_attempts: u32,
+    /// The delay before retrying a failed attempt, doubled after every failed attempt.
+    pub initial_backoff: Duration,
+    /// The maximum delay between two attempts.
+    pub max_backoff: Duration,
+    /// The time after which a request without response times out.
+    ///
+    /// An endpoint which times out repeatedly is failed over from.
+    pub request_timeout: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            request_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// The number of consecutive timed out requests after which an endpoint is considered
+/// unresponsive, and the client fails over to the next endpoint.
+#[cfg(feature = "native")]
+const MAX_CONSECUTIVE_TIMEOUTS: u32 = 3;
+
+/// WebSocket client which re-establishes the connection to the node when it is dropped.
+///
+/// Requests and subscriptions which fail because the connection was dropped, or which time
+/// out on an endpoint which has stopped responding, are retried once the connection is
+/// re-established. With multiple endpoints, the client stays connected to one endpoint
+/// until its connection fails or it stops responding, and then fails over to the next one.
+#[cfg(feature = "native")]
+pub struct ReconnectingWsClient {
+    urls: Vec<String>,
+    policy: ReconnectPolicy,
+    client: Mutex<(usize, Arc<WsClient>)>,
+    reconnections: AtomicU32,
+    timeouts: AtomicU32,
+}
+
+#[cfg(feature = "native")]
+impl ReconnectingWsClient {
+    /// Connect to the node at the given URL.
+    pub async fn connect(url: &str, policy: ReconnectPolicy) -> Result<Self, Error> {
+        Self::connect_any(vec![url.to_owned()], policy).await
+    }
+
+    /// Connect to the first reachable node of the given URLs, trying them in order.
+    pub async fn connect_any(
+        urls: Vec<String>,
+        policy: ReconnectPolicy,
+    ) -> Result<Self, Error> {
+        if urls.is_empty() {
+            return Err("No rpc endpoints given".into())
+        }
+        let (active, client) = Self::connect_with_backoff(&urls, 0, &policy).await?;
+        Ok(Self {
+            urls,
+            policy,
+            client: Mutex::new((active, Arc::new(client))),
+            reconnections: AtomicU32::new(0),
+            timeouts: AtomicU32::new(0),
+        })
+    }
+
+    /// Returns the number of times the connection has been re-established.
+    pub fn reconnections(&self) -> u32 {
+        self.reconnections.load(Ordering::SeqCst)
+    }
+
+    /// Returns the URL of the endpoint the client is connected to.
+    pub async fn active_url(&self) -> String {
+        let client = self.client.lock().await;
+        self.urls[client.0].clone()
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &[JsonValue],
+    ) -> Result<T, Error> {
+        let client = self.client.lock().await.1.clone();
+        let result = client.request(method, params.into()).await;
+        if self.connection_failed(&result) {
+            let client = self.reconnect(&client).await?;
+            return client
+                .request(method, params.into())
+                .await
+                .map_err(Into::into)
+        }
+        result.map_err(Into::into)
+    }
+
+    async fn subscribe<T: DeserializeOwned>(
+        &self,
+        subscribe_method: &str,
+        params: &[JsonValue],
+        unsubscribe_method: &str,
+    ) -> Result<Subscription<T>, Error> {
+        let client = self.client.lock().await.1.clone();
+        let result = client
+            .subscribe(subscribe_method, params.into(), unsubscribe_method)
+            .await;
+        if self.connection_failed(&result) {
+            let client = self.reconnect(&client).await?;
+            return client
+                .subscribe(subscribe_method, params.into(), unsubscribe_method)
+                .await
+                .map_err(Into::into)
+        }
+        result.map_err(Into::into)
+    }
+
+    /// Returns `true` if the request with the given result failed because the connection
+    /// was dropped, or because it timed out and the endpoint has stopped responding.
+    ///
+    /// Consecutive timeouts are counted, so that the client fails over after
+    /// [`MAX_CONSECUTIVE_TIMEOUTS`] of them.
+    fn connection_failed<R>(&self, result: &Result<R, RpcError>) -> bool {
+        match result {
+            Err(RpcError::RestartNeeded(_)) => true,
+            Err(RpcError::RequestTimeout) => {
+                self.timeouts.fetch_add(1, Ordering::SeqCst) + 1
+                    >= MAX_CONSECUTIVE_TIMEOUTS
+            }
+            _ => {
+                self.timeouts.store(0, Ordering::SeqCst);
+                false
+            }
+        }
+    }
+
+    /// Replace the failed client with a new connection, unless another caller has already
+    /// done so.
+    ///
+    /// The next endpoint is tried first, so that a failed endpoint is only reconnected to
+    /// once the other ones have failed too.
+    async fn reconnect(&self, failed: &Arc<WsClient>) -> Result<Arc<WsClient>, Error> {
+        let mut client = self.client.lock().await;
+        if Arc::ptr_eq(&client.1, failed) {
+            let (active, _) = *client;
+            log::warn!("Connection to {} failed, reconnecting", self.urls[active]);
+            let next = (active + 1) % self.urls.len();
+            let (active, new_client) =
+                Self::connect_with_backoff(&self.urls, next, &self.policy).await?;
+            *client = (active, Arc::new(new_client));
+            self.reconnections.fetch_add(1, Ordering::SeqCst);
+            self.timeouts.store(0, Ordering::SeqCst);
+        }
+        Ok(client.1.clone())
+    }
+
+    /// Connect to the endpoints in turn starting with the one at index `start`, returning
+    /// the index of the connected endpoint.
+    async fn connect_with_backoff(
+        urls: &[String],
+        start: usize,
+        policy: &ReconnectPolicy,
+    ) -> Result<(usize, WsClient), Error> {
+        let mut backoff = policy.initial_backoff;
+        let mut attempts = 1;
+        let mut index = start;
+        loop {
+            let url = &urls[index];
+            match WsClientBuilder::default()
+                .max_notifs_per_subscription(4096)
+                .request_timeout(policy.request_timeout)
+                .build(url)
+                .await
+            {
+                Ok(client) => return Ok((index, client)),
+                Err(err) if attempts < policy.max_attempts => {
+                    log::debug!(
+                        "Connecting to {} failed, attempt {}/{}: {}",
+                        url,
+                        attempts,
+                        policy.max_attempts,
+                        err
+                    );
+                    Delay::new(backoff).await;
+                    backoff = (backoff * 2).min(policy.max_backoff);
+                    attempts += 1;
+                    index = (index + 1) % urls.len();
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "native")]
 impl From<WsClient> for RpcClient {
     fn from(client: WsClient) -> Self {
         RpcClient::WebSocket(Arc::new(client))
     }
 }
 
+#[cfg(feature = "native")]
 impl From<Arc<WsClient>> for RpcClient {
     fn from(client: Arc<WsClient>) -> Self {
         RpcClient::WebSocket(client)
     }
 }
 
+#[cfg(feature = "native")]
 impl From<HttpClient> for RpcClient {
     fn from(client: HttpClient) -> Self {
         RpcClient::Http(Arc::new(client))
     }
 }
 
+#[cfg(feature = "native")]
 impl From<Arc<HttpClient>> for RpcClient {
     fn from(client: Arc<HttpClient>) -> Self {
         RpcClient::Http(client)
     }
 }
 
+#[cfg(feature = "wasm")]
+impl From<BrowserWsClient> for RpcClient {
+    fn from(client: BrowserWsClient) -> Self {
+        RpcClient::Browser(Arc::new(client))
+    }
+}
+
+impl From<Arc<dyn RpcTransport>> for RpcClient {
+    fn from(transport: Arc<dyn RpcTransport>) -> Self {
+        RpcClient::Custom(transport)
+    }
+}
+
 /// ReadProof struct returned by the RPC
 ///
 /// # Note
@@ -329,6 +993,24 @@ impl<T: Config> Rpc<T> {
         self.accept_weak_inclusion = true;
     }
 
+    /// Invoke an arbitrary JSON-RPC method, e.g. one not exposed by a dedicated wrapper.
+    ///
+    /// The `params` are serialized to JSON, where a tuple or sequence is passed as the list
+    /// of positional parameters, `()` as no parameters and any other value as the single
+    /// parameter.
+    pub async fn request<P, R>(&self, method: &str, params: P) -> Result<R, Error>
+    where
+        P: Serialize,
+        R: DeserializeOwned + std::fmt::Debug,
+    {
+        let params = match to_json_value(params)? {
+            JsonValue::Array(params) => params,
+            JsonValue::Null => Vec::new(),
+            param => vec![param],
+        };
+        self.client.request(method, &params).await
+    }
+
     /// Fetch a storage key
     pub async fn storage(
         &self,
@@ -340,6 +1022,18 @@ impl<T: Config> Rpc<T> {
         Ok(data)
     }
 
+    /// Fetch the size in bytes of the raw value under the storage key, without fetching the
+    /// value.
+    pub async fn storage_size(
+        &self,
+        key: &StorageKey,
+        hash: Option<T::Hash>,
+    ) -> Result<Option<u64>, Error> {
+        let params = &[to_json_value(key)?, to_json_value(hash)?];
+        let size = self.client.request("state_getStorageSize", params).await?;
+        Ok(size)
+    }
+
     /// Returns the keys with prefix with pagination support.
     /// Up to `count` keys will be returned.
     /// If `start_key` is passed, return next keys in storage in lexicographic order.
@@ -392,6 +1086,20 @@ impl<T: Config> Rpc<T> {
             .map_err(Into::into)
     }
 
+    /// Fetch the next nonce to use for an account, taking into account any transactions from
+    /// that account which are pending in the transaction pool.
+    ///
+    /// The `nonce` of the `System::Account` storage entry of the account only reflects the
+    /// transactions included on chain.
+    pub async fn account_nonce(&self, account: &T::AccountId) -> Result<T::Index, Error> {
+        let params = &[to_json_value(account)?];
+        let nonce = self
+            .client
+            .request("system_accountNextIndex", params)
+            .await?;
+        Ok(nonce)
+    }
+
     /// Fetch the genesis hash
     pub async fn genesis_hash(&self) -> Result<T::Hash, Error> {
         let block_zero = Some(ListOrValue::Value(NumberOrHex::Number(0)));
@@ -406,12 +1114,17 @@ impl<T: Config> Rpc<T> {
         }
     }
 
+    /// Fetch the raw SCALE encoded metadata, e.g. to save it to a file for the
+    /// `runtime_metadata_path` of the `subxt` macro.
+    pub async fn metadata_bytes(&self) -> Result<Vec<u8>, Error> {
+        let bytes: Bytes = self.client.request("state_getMetadata", &[]).await?;
+        Ok(bytes.0)
+    }
+
     /// Fetch the metadata
     pub async fn metadata(&self) -> Result<Metadata, Error> {
-        let bytes: Bytes = self.client.request("state_getMetadata", &[]).await?;
-        let meta: RuntimeMetadataPrefixed = Decode::decode(&mut &bytes[..])?;
-        let metadata: Metadata = meta.try_into()?;
-        Ok(metadata)
+        let bytes = self.metadata_bytes().await?;
+        Metadata::from_bytes(&bytes)
     }
 
     /// Fetch system properties
@@ -419,6 +1132,31 @@ impl<T: Config> Rpc<T> {
         Ok(self.client.request("system_properties", &[]).await?)
     }
 
+    /// Fetch the name of the chain, e.g. "Development"
+    pub async fn system_chain(&self) -> Result<String, Error> {
+        Ok(self.client.request("system_chain", &[]).await?)
+    }
+
+    /// Fetch the name of the node implementation, e.g. "Substrate Node"
+    pub async fn system_name(&self) -> Result<String, Error> {
+        Ok(self.client.request("system_name", &[]).await?)
+    }
+
+    /// Fetch the version of the node implementation
+    pub async fn system_version(&self) -> Result<String, Error> {
+        Ok(self.client.request("system_version", &[]).await?)
+    }
+
+    /// Fetch the health of the node
+    pub async fn system_health(&self) -> Result<Health, Error> {
+        Ok(self.client.request("system_health", &[]).await?)
+    }
+
+    /// Fetch the sync status of the node
+    pub async fn system_sync_state(&self) -> Result<SyncState, Error> {
+        Ok(self.client.request("system_syncState", &[]).await?)
+    }
+
     /// Get a header
     pub async fn header(
         &self,
@@ -483,19 +1221,96 @@ impl<T: Config> Rpc<T> {
         Ok(version)
     }
 
+    /// Call a runtime API function, e.g. `Core_version`, with the SCALE encoded arguments,
+    /// returning the SCALE encoded result.
+    pub async fn state_call(
+        &self,
+        method: &str,
+        data: &[u8],
+        at: Option<T::Hash>,
+    ) -> Result<Vec<u8>, Error> {
+        let bytes: Bytes = data.to_vec().into();
+        let params = &[
+            to_json_value(method)?,
+            to_json_value(bytes)?,
+            to_json_value(at)?,
+        ];
+        let result: Bytes = self.client.request("state_call", params).await?;
+        Ok(result.0)
+    }
+
+    /// Call a runtime API function, encoding the arguments and decoding the result.
+    ///
+    /// Multiple arguments are passed as a tuple, e.g. `&(account_id,)`.
+    pub async fn state_call_decoded<A: Encode, R: Decode>(
+        &self,
+        method: &str,
+        args: &A,
+        at: Option<T::Hash>,
+    ) -> Result<R, Error> {
+        let bytes = self.state_call(method, &args.encode(), at).await?;
+        Ok(Decode::decode(&mut &bytes[..])?)
+    }
+
+    /// Subscribe to runtime version updates, which are published on runtime upgrades.
+    ///
+    /// The current runtime version is sent when subscribing.
+    pub async fn subscribe_runtime_version(
+        &self,
+    ) -> Result<Subscription<RuntimeVersion>, Error> {
+        let subscription = self
+            .client
+            .subscribe(
+                "state_subscribeRuntimeVersion",
+                &[],
+                "state_unsubscribeRuntimeVersion",
+            )
+            .await?;
+        Ok(subscription)
+    }
+
     /// Subscribe to System Events that are imported into blocks.
     ///
     /// *WARNING* these may not be included in the finalized chain, use
     /// `subscribe_finalized_events` to ensure events are finalized.
     pub async fn subscribe_events(&self) -> Result<EventStorageSubscription<T>, Error> {
-        let keys = Some(vec![StorageKey::from(SystemEvents::new())]);
-        let params = &[to_json_value(keys)?];
+        let keys = vec![StorageKey::from(SystemEvents::new())];
+        if self.client.is_reconnecting() {
+            Ok(EventStorageSubscription::Reconnecting(
+                self.subscribe_storage(keys).await?,
+            ))
+        } else {
+            Ok(EventStorageSubscription::Imported(
+                self.subscribe_storage_changes(&keys).await?,
+            ))
+        }
+    }
+
+    /// Subscribe to changes of the given storage keys in imported blocks.
+    ///
+    /// With a reconnecting client, the subscription is re-established after reconnecting.
+    pub async fn subscribe_storage(
+        &self,
+        keys: Vec<StorageKey>,
+    ) -> Result<StorageChangeSetSubscription<T>, Error> {
+        let subscription = self.subscribe_storage_changes(&keys).await?;
+        Ok(StorageChangeSetSubscription::new(
+            self.clone(),
+            keys,
+            subscription,
+        ))
+    }
 
+    pub(crate) async fn subscribe_storage_changes(
+        &self,
+        keys: &[StorageKey],
+    ) -> Result<Subscription<StorageChangeSet<T::Hash>>, Error> {
+        let params = &[to_json_value(Some(keys))?];
         let subscription = self
             .client
             .subscribe("state_subscribeStorage", params, "state_unsubscribeStorage")
             .await?;
-        Ok(EventStorageSubscription::Imported(subscription))
+        Ok(subscription)
     }
 
     /// Subscribe to finalized events.
@@ -549,6 +1364,32 @@ impl<T: Config> Rpc<T> {
         Ok(xt_hash)
     }
 
+    /// Execute a dry run of an encoded extrinsic against the state at the given block,
+    /// without submitting it to the transaction pool.
+    pub async fn dry_run(
+        &self,
+        encoded_signed: &[u8],
+        at: Option<T::Hash>,
+    ) -> Result<ApplyExtrinsicResult, Error> {
+        let bytes: Bytes = encoded_signed.to_vec().into();
+        let params = &[to_json_value(bytes)?, to_json_value(at)?];
+        let result_bytes: Bytes = self.client.request("system_dryRun", params).await?;
+        let result: ApplyExtrinsicResult = Decode::decode(&mut &result_bytes[..])?;
+        Ok(result)
+    }
+
+    /// Query the dispatch info, including the fee, of an encoded extrinsic at the given block.
+    pub async fn query_info(
+        &self,
+        encoded_signed: &[u8],
+        at: Option<T::Hash>,
+    ) -> Result<RuntimeDispatchInfo, Error> {
+        let bytes: Bytes = encoded_signed.to_vec().into();
+        let params = &[to_json_value(bytes)?, to_json_value(at)?];
+        let info = self.client.request("payment_queryInfo", params).await?;
+        Ok(info)
+    }
+
     /// Create and submit an extrinsic and return a subscription to the events triggered.
     pub async fn watch_extrinsic<E: Encode>(
         &self,
@@ -567,55 +1408,100 @@ impl<T: Config> Rpc<T> {
         Ok(subscription)
     }
 
+    /// Create and submit an extrinsic and return a stream of all its status updates, which
+    /// ends after a final status.
+    pub async fn watch_extrinsic_status<E: Encode>(
+        &self,
+        extrinsic: E,
+    ) -> Result<TransactionStatusStream<T>, Error> {
+        let subscription = self.watch_extrinsic(extrinsic).await?;
+        let statuses = stream::unfold(Some(subscription), |subscription| {
+            async move {
+                let mut subscription = subscription?;
+                match subscription.next().await {
+                    Ok(Some(status)) => {
+                        let subscription = if status.is_final() {
+                            None
+                        } else {
+                            Some(subscription)
+                        };
+                        Some((Ok(status), subscription))
+                    }
+                    Ok(None) => None,
+                    Err(err) => Some((Err(err.into()), None)),
+                }
+            }
+        });
+        Ok(statuses.boxed())
+    }
+
     /// Create and submit an extrinsic and return corresponding Event if successful
     pub async fn submit_and_watch_extrinsic<'a, E: Encode + 'static>(
         &self,
         extrinsic: E,
         decoder: &'a EventsDecoder<T>,
+    ) -> Result<ExtrinsicSuccess<T>, Error> {
+        self.submit_and_watch_extrinsic_until(
+            extrinsic,
+            decoder,
+            self.accept_weak_inclusion,
+        )
+        .await
+    }
+
+    /// Create and submit an extrinsic and return corresponding Event once the block it was
+    /// included in has been finalized, even if the Rpc is configured to accept non-finalized
+    /// blocks.
+    pub async fn submit_and_watch_extrinsic_finalized<'a, E: Encode + 'static>(
+        &self,
+        extrinsic: E,
+        decoder: &'a EventsDecoder<T>,
+    ) -> Result<ExtrinsicSuccess<T>, Error> {
+        self.submit_and_watch_extrinsic_until(extrinsic, decoder, false)
+            .await
+    }
+
+    async fn submit_and_watch_extrinsic_until<'a, E: Encode + 'static>(
+        &self,
+        extrinsic: E,
+        decoder: &'a EventsDecoder<T>,
+        accept_weak_inclusion: bool,
     ) -> Result<ExtrinsicSuccess<T>, Error> {
         let ext_hash = T::Hashing::hash_of(&extrinsic);
         log::info!("Submitting Extrinsic `{:?}`", ext_hash);
 
-        let events_sub = if self.accept_weak_inclusion {
+        let events_sub = if accept_weak_inclusion {
             self.subscribe_events().await
         } else {
             self.subscribe_finalized_events().await
         }?;
-        let mut xt_sub = self.watch_extrinsic(extrinsic).await?;
-
-        while let Ok(Some(status)) = xt_sub.next().await {
-            log::info!("received status {:?}", status);
-            match status {
-                // ignore in progress extrinsic for now
-                TransactionStatus::Future
-                | TransactionStatus::Ready
-                | TransactionStatus::Broadcast(_) => continue,
-                TransactionStatus::InBlock(block_hash) => {
-                    if self.accept_weak_inclusion {
-                        return self
-                            .process_block(events_sub, decoder, block_hash, ext_hash)
-                            .await
-                    }
-                    continue
-                }
-                TransactionStatus::Invalid => return Err("Extrinsic Invalid".into()),
-                TransactionStatus::Usurped(_) => return Err("Extrinsic Usurped".into()),
-                TransactionStatus::Dropped => return Err("Extrinsic Dropped".into()),
-                TransactionStatus::Retracted(_) => {
-                    return Err("Extrinsic Retracted".into())
-                }
-                TransactionStatus::Finalized(block_hash) => {
-                    // read finalized blocks by default
-                    return self
-                        .process_block(events_sub, decoder, block_hash, ext_hash)
-                        .await
-                }
-                TransactionStatus::FinalityTimeout(_) => {
-                    return Err("Extrinsic FinalityTimeout".into())
-                }
-            }
-        }
-        Err(RpcError::Custom("RPC subscription dropped".into()).into())
+        let statuses = self.watch_extrinsic_status(extrinsic).await?;
+        let inclusion = wait_for_inclusion(statuses, accept_weak_inclusion).await?;
+        self.process_block(
+            events_sub,
+            decoder,
+            inclusion.block,
+            inclusion.in_block,
+            inclusion.finalized,
+            ext_hash,
+        )
+        .await
+    }
+
+    /// Submit an already SCALE encoded extrinsic and return corresponding Hash if successful.
+    pub async fn submit_extrinsic_bytes(&self, bytes: Vec<u8>) -> Result<T::Hash, Error> {
+        self.submit_extrinsic(Encoded(bytes)).await
+    }
+
+    /// Submit an already SCALE encoded extrinsic and return corresponding Event if
+    /// successful.
+    pub async fn submit_and_watch_extrinsic_bytes<'a>(
+        &self,
+        bytes: Vec<u8>,
+        decoder: &'a EventsDecoder<T>,
+    ) -> Result<ExtrinsicSuccess<T>, Error> {
+        self.submit_and_watch_extrinsic(Encoded(bytes), decoder)
+            .await
     }
 
     async fn process_block<'a>(
@@ -623,6 +1509,8 @@ impl<T: Config> Rpc<T> {
         events_sub: EventStorageSubscription<T>,
         decoder: &'a EventsDecoder<T>,
         block_hash: T::Hash,
+        in_block: T::Hash,
+        finalized: Option<T::Hash>,
         ext_hash: T::Hash,
     ) -> Result<ExtrinsicSuccess<T>, Error> {
         log::info!("Fetching block {:?}", block_hash);
@@ -655,13 +1543,21 @@ impl<T: Config> Rpc<T> {
             Ok(ExtrinsicSuccess {
                 block: block_hash,
                 extrinsic: ext_hash,
+                index: ext_index as u32,
                 events,
+                in_block,
+                finalized,
             })
         } else {
             Err(format!("Failed to find block {:?}", block_hash).into())
         }
     }
 
+    /// Fetch the SCALE encoded extrinsics currently in the transaction pool of the node.
+    pub async fn pending_extrinsics(&self) -> Result<Vec<Bytes>, Error> {
+        Ok(self.client.request("author_pendingExtrinsics", &[]).await?)
+    }
+
     /// Insert a key into the keystore.
     pub async fn insert_key(
         &self,
@@ -706,18 +1602,116 @@ impl<T: Config> Rpc<T> {
     }
 }
 
+/// The blocks a submitted extrinsic was included in, as reported by its status updates.
+#[derive(Debug, PartialEq)]
+struct Inclusion<Hash> {
+    /// The block to read the events of the extrinsic from.
+    block: Hash,
+    /// The block the extrinsic was last reported `InBlock` in.
+    in_block: Hash,
+    /// The finalized block, if its finalization was waited for.
+    finalized: Option<Hash>,
+}
+
+/// Wait for the status updates of a submitted extrinsic to report it `InBlock` if
+/// `accept_weak_inclusion`, or `Finalized` otherwise.
+///
+/// While waiting for finality, a block including the extrinsic may be retracted in a reorg,
+/// after which the extrinsic is included again in another block, so a `Retracted` status
+/// only forgets the retracted block. The statuses after which the extrinsic is not included
+/// anymore are returned as a [`TransactionError`].
+async fn wait_for_inclusion<Hash, S>(
+    mut statuses: S,
+    accept_weak_inclusion: bool,
+) -> Result<Inclusion<Hash>, Error>
+where
+    Hash: Copy + AsRef<[u8]> + std::fmt::Debug,
+    S: Stream<Item = Result<TransactionStatus<Hash, Hash>, Error>> + Unpin,
+{
+    let mut in_block = None;
+    while let Some(status) = statuses.next().await {
+        let status = status?;
+        log::info!("received status {:?}", status);
+        match status {
+            // ignore in progress extrinsic for now
+            TransactionStatus::Future
+            | TransactionStatus::Ready
+            | TransactionStatus::Broadcast(_) => continue,
+            TransactionStatus::InBlock(block_hash) => {
+                if accept_weak_inclusion {
+                    return Ok(Inclusion {
+                        block: block_hash,
+                        in_block: block_hash,
+                        finalized: None,
+                    })
+                }
+                in_block = Some(block_hash);
+            }
+            TransactionStatus::Retracted(block_hash) => {
+                log::info!("Block {:?} including the extrinsic retracted", block_hash);
+                in_block = None;
+            }
+            TransactionStatus::Finalized(block_hash) => {
+                // read finalized blocks by default
+                return Ok(Inclusion {
+                    block: block_hash,
+                    in_block: in_block.unwrap_or(block_hash),
+                    finalized: Some(block_hash),
+                })
+            }
+            TransactionStatus::Invalid => return Err(TransactionError::Invalid.into()),
+            TransactionStatus::Usurped(hash) => {
+                return Err(TransactionError::Usurped(hash.as_ref().to_vec()).into())
+            }
+            TransactionStatus::Dropped => return Err(TransactionError::Dropped.into()),
+            TransactionStatus::FinalityTimeout(block_hash) => {
+                return Err(TransactionError::FinalityTimeout(
+                    block_hash.as_ref().to_vec(),
+                )
+                .into())
+            }
+        }
+    }
+    Err(RpcError::Custom("RPC subscription dropped".into()).into())
+}
+
 /// Captures data for when an extrinsic is successfully included in a block
 #[derive(Debug)]
 pub struct ExtrinsicSuccess<T: Config> {
-    /// Block hash.
+    /// Hash of the block the events were read from.
     pub block: T::Hash,
     /// Extrinsic hash.
     pub extrinsic: T::Hash,
+    /// Index of the extrinsic in the block.
+    pub index: u32,
     /// Raw runtime events, can be decoded by the caller.
     pub events: Vec<RawEvent>,
+    /// Hash of the block the extrinsic was last reported `InBlock` in.
+    in_block: T::Hash,
+    /// Hash of the finalized block containing the extrinsic, if its finalization was waited
+    /// for.
+    finalized: Option<T::Hash>,
 }
 
 impl<T: Config> ExtrinsicSuccess<T> {
+    /// Returns the hash of the block the extrinsic was last reported `InBlock` in.
+    ///
+    /// If a block including the extrinsic was retracted in a reorg, and the extrinsic was
+    /// included again in another block, this is the hash of the latest such block. It may
+    /// still differ from the finalized block, if another reorg happened before
+    /// finalization.
+    pub fn block_hash(&self) -> T::Hash {
+        self.in_block
+    }
+
+    /// Returns the hash of the finalized block containing the extrinsic, or `None` if only
+    /// its inclusion was waited for, see [`crate::ClientBuilder::accept_weak_inclusion`].
+    ///
+    /// The [`events`](Self::events) are read from this block if it is `Some`.
+    pub fn finalized_hash(&self) -> Option<T::Hash> {
+        self.finalized
+    }
+
     /// Find the Event for the given module/variant, with raw encoded event data.
     /// Returns `None` if the Event is not found.
     pub fn find_event_raw(&self, module: &str, variant: &str) -> Option<&RawEvent> {
@@ -736,4 +1730,171 @@ impl<T: Config> ExtrinsicSuccess<T> {
             Ok(None)
         }
     }
+
+    /// Fetch all events of the block of the extrinsic, grouped by the phase in which they
+    /// were emitted, in the order of the block.
+    ///
+    /// The events of this extrinsic are in the group of `Phase::ApplyExtrinsic(self.index)`.
+    pub async fn block_events(
+        &self,
+        client: &crate::Client<T>,
+    ) -> Result<Vec<(Phase, Vec<Raw>)>, Error> {
+        let mut groups: Vec<(Phase, Vec<Raw>)> = Vec::new();
+        for record in client.events_at(self.block).await? {
+            match groups.last_mut() {
+                Some((phase, events)) if *phase == record.phase => {
+                    events.push(record.event)
+                }
+                _ => groups.push((record.phase, vec![record.event])),
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Find the `System::ExtrinsicSuccess` event, and decode the weight, class and fee
+    /// payment of the extrinsic, with the shape of the weight taken from the metadata.
+    ///
+    /// Returns `None` if the event is not found.
+    pub fn dispatch_info(
+        &self,
+        metadata: &Metadata,
+    ) -> Result<Option<DispatchInfo>, Error> {
+        if let Some(event) = self.find_event_raw("System", "ExtrinsicSuccess") {
+            let info =
+                DispatchInfo::decode_with_metadata(&mut &event.data[..], metadata)?;
+            Ok(Some(info))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Find the `Proxy::ProxyExecuted` event, and decode the result of the call dispatched
+    /// on behalf of the proxied account.
+    ///
+    /// Returns `None` if the event is not found, i.e. the extrinsic was not a proxy call.
+    pub fn proxy_executed(
+        &self,
+    ) -> Result<Option<Result<(), DispatchError>>, CodecError> {
+        if let Some(event) = self.find_event_raw("Proxy", "ProxyExecuted") {
+            let result = <Result<(), DispatchError>>::decode(&mut &event.data[..])?;
+            Ok(Some(result))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Find the `Utility::BatchInterrupted` event, if the extrinsic was a batch which
+    /// failed part way through.
+    ///
+    /// Returns `None` if the event is not found, i.e. all calls in the batch succeeded or the
+    /// extrinsic was not a batch.
+    pub fn batch_interrupted(&self) -> Result<Option<BatchInterrupted>, CodecError> {
+        if let Some(event) = self.find_event_raw("Utility", "BatchInterrupted") {
+            let (index, error) = <(u32, DispatchError)>::decode(&mut &event.data[..])?;
+            Ok(Some(BatchInterrupted { index, error }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Find the error of a call dispatched by the extrinsic which failed without failing the
+    /// extrinsic itself, as reported by `Utility::BatchInterrupted`, `Proxy::ProxyExecuted` or
+    /// `Sudo::Sudid`.
+    ///
+    /// The pallet error of a [`DispatchError::Module`] can be looked up in the metadata with
+    /// [`RuntimeError::from_dispatch`](crate::RuntimeError::from_dispatch).
+    ///
+    /// Returns `None` if no such call failed.
+    pub fn dispatch_error(&self) -> Result<Option<DispatchError>, CodecError> {
+        if let Some(interrupted) = self.batch_interrupted()? {
+            return Ok(Some(interrupted.error))
+        }
+        if let Some(Err(error)) = self.proxy_executed()? {
+            return Ok(Some(error))
+        }
+        if let Some(event) = self.find_event_raw("Sudo", "Sudid") {
+            if let Err(error) = <Result<(), DispatchError>>::decode(&mut &event.data[..])?
+            {
+                return Ok(Some(error))
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Details of a `Utility::batch` which was interrupted by a failing call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchInterrupted {
+    /// The index of the failing call in the batch.
+    pub index: u32,
+    /// The error with which the call failed.
+    pub error: DispatchError,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_core::H256;
+
+    type Status = TransactionStatus<H256, H256>;
+
+    fn wait_for(
+        statuses: Vec<Status>,
+        accept_weak_inclusion: bool,
+    ) -> Result<Inclusion<H256>, Error> {
+        let statuses = stream::iter(statuses.into_iter().map(Ok));
+        futures::executor::block_on(wait_for_inclusion(statuses, accept_weak_inclusion))
+    }
+
+    #[test]
+    fn wait_for_finalization_after_retracted_block() {
+        let (a, b) = (H256::repeat_byte(1), H256::repeat_byte(2));
+        let statuses = vec![
+            Status::Ready,
+            Status::InBlock(a),
+            Status::Retracted(a),
+            Status::InBlock(b),
+            Status::Finalized(b),
+        ];
+        let inclusion = wait_for(statuses, false).unwrap();
+        // `ExtrinsicSuccess::block_hash` is the latest `InBlock` hash
+        assert_eq!(
+            inclusion,
+            Inclusion {
+                block: b,
+                in_block: b,
+                finalized: Some(b),
+            }
+        );
+    }
+
+    #[test]
+    fn wait_for_weak_inclusion() {
+        let a = H256::repeat_byte(1);
+        let statuses = vec![Status::Ready, Status::InBlock(a), Status::Retracted(a)];
+        let inclusion = wait_for(statuses, true).unwrap();
+        assert_eq!(
+            inclusion,
+            Inclusion {
+                block: a,
+                in_block: a,
+                finalized: None,
+            }
+        );
+    }
+
+    #[test]
+    fn wait_for_inclusion_fails_on_final_status() {
+        let (a, b) = (H256::repeat_byte(1), H256::repeat_byte(2));
+        let statuses = vec![Status::InBlock(a), Status::Retracted(a), Status::Usurped(b)];
+        let result = wait_for(statuses, false);
+        assert!(matches!(
+            result,
+            Err(Error::Transaction(TransactionError::Usurped(hash))) if hash == b.as_bytes()
+        ));
+
+        // the subscription ends without a final status
+        let result = wait_for(vec![Status::InBlock(a)], false);
+        assert!(matches!(result, Err(Error::Rpc(_))));
+    }
 }
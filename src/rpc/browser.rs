@@ -0,0 +1,424 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+//! JSON-RPC client over the WebSocket of the browser, for running in `wasm32` frontends
+//! where the sockets of the native jsonrpsee clients are not available.
+
+use std::collections::HashMap;
+
+use futures::{
+    channel::{
+        mpsc,
+        oneshot,
+    },
+    select,
+    StreamExt,
+};
+use jsonrpsee_types::{
+    v2::params::SubscriptionId,
+    DeserializeOwned,
+    Error as RpcError,
+    FrontToBack,
+    JsonValue,
+    Subscription,
+    SubscriptionKind,
+};
+use wasm_bindgen::{
+    closure::Closure,
+    JsCast,
+};
+use web_sys::{
+    CloseEvent,
+    Event,
+    MessageEvent,
+    WebSocket,
+};
+
+use crate::error::Error;
+
+/// The maximum number of notifications buffered for a subscription which are not yet
+/// consumed, like `max_notifs_per_subscription` of the native WebSocket client.
+const MAX_NOTIFS_PER_SUBSCRIPTION: usize = 4096;
+
+/// The maximum number of dropped subscriptions which are not yet unsubscribed from.
+const MAX_CLOSED_SUBSCRIPTIONS: usize = 256;
+
+/// WebSocket client using the `WebSocket` of the browser.
+///
+/// The socket is owned by a task on the local executor of the page, which the client sends
+/// its requests to, so that the client itself is `Send` and `Sync` like the native clients
+/// although the socket is not.
+pub struct BrowserWsClient {
+    to_back: mpsc::UnboundedSender<Message>,
+    // dropped subscriptions send their unsubscribe message here
+    subscriptions_closed: mpsc::Sender<FrontToBack>,
+}
+
+impl BrowserWsClient {
+    /// Connect to the node at the given WebSocket URL.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        let socket = WebSocket::new(url).map_err(|err| js_error("Connecting", err))?;
+        let (opened_tx, mut opened) = oneshot::channel();
+        let mut opened_tx = Some(opened_tx);
+        let on_open: Box<dyn FnMut(Event)> = Box::new(move |_| {
+            if let Some(opened_tx) = opened_tx.take() {
+                let _ = opened_tx.send(());
+            }
+        });
+        let on_open = Closure::wrap(on_open);
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+
+        let (incoming_tx, mut incoming) = mpsc::unbounded();
+        let on_message: Box<dyn FnMut(MessageEvent)> = {
+            let incoming_tx = incoming_tx.clone();
+            Box::new(move |event| {
+                if let Some(text) = event.data().as_string() {
+                    let _ = incoming_tx.unbounded_send(Incoming::Text(text));
+                }
+            })
+        };
+        let on_message = Closure::wrap(on_message);
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        let on_close: Box<dyn FnMut(CloseEvent)> = Box::new(move |event| {
+            let _ = incoming_tx.unbounded_send(Incoming::Closed(event.reason()));
+        });
+        let on_close = Closure::wrap(on_close);
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        // a socket which fails to connect is closed without being opened
+        select! {
+            _ = opened => {}
+            closed = incoming.next() => {
+                let reason = match closed {
+                    Some(Incoming::Closed(reason)) => reason,
+                    _ => String::new(),
+                };
+                return Err(RpcError::Custom(format!(
+                    "Connecting to {} failed: {}",
+                    url, reason
+                ))
+                .into())
+            }
+        }
+        socket.set_onopen(None);
+        drop(on_open);
+
+        let (to_back, from_front) = mpsc::unbounded();
+        let (subscriptions_closed, closed) = mpsc::channel(MAX_CLOSED_SUBSCRIPTIONS);
+        let background = Background {
+            socket,
+            next_id: 0,
+            pending: HashMap::new(),
+            subscriptions: HashMap::new(),
+            _closures: (on_message, on_close),
+        };
+        wasm_bindgen_futures::spawn_local(background.run(from_front, closed, incoming));
+        Ok(Self {
+            to_back,
+            subscriptions_closed,
+        })
+    }
+
+    /// Send a JSON-RPC request, returning the result of the response.
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &[JsonValue],
+    ) -> Result<T, Error> {
+        let (send_back, response) = oneshot::channel();
+        self.send(Message::Request {
+            method: method.to_owned(),
+            params: params.to_vec(),
+            send_back,
+        })?;
+        let result = response.await.map_err(|_| connection_closed())??;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Start a JSON-RPC subscription.
+    ///
+    /// Dropping the returned [`Subscription`] calls `unsubscribe_method` on the node, so
+    /// the node stops producing notifications for it.
+    pub async fn subscribe<T: DeserializeOwned>(
+        &self,
+        subscribe_method: &str,
+        params: &[JsonValue],
+        unsubscribe_method: &str,
+    ) -> Result<Subscription<T>, Error> {
+        let (send_back, response) = oneshot::channel();
+        self.send(Message::Subscribe {
+            method: subscribe_method.to_owned(),
+            params: params.to_vec(),
+            unsubscribe_method: unsubscribe_method.to_owned(),
+            send_back,
+        })?;
+        let (id, notifs) = response.await.map_err(|_| connection_closed())??;
+        Ok(Subscription::new(
+            self.subscriptions_closed.clone(),
+            notifs,
+            SubscriptionKind::Subscription(id),
+        ))
+    }
+
+    fn send(&self, message: Message) -> Result<(), Error> {
+        self.to_back
+            .unbounded_send(message)
+            .map_err(|_| connection_closed().into())
+    }
+}
+
+/// Messages from the client to the task owning the socket.
+enum Message {
+    Request {
+        method: String,
+        params: Vec<JsonValue>,
+        send_back: oneshot::Sender<Result<JsonValue, RpcError>>,
+    },
+    Subscribe {
+        method: String,
+        params: Vec<JsonValue>,
+        unsubscribe_method: String,
+        send_back: oneshot::Sender<SubscribeResult>,
+    },
+}
+
+/// The id of a new subscription, and the channel of its notifications.
+type SubscribeResult = Result<(SubscriptionId, mpsc::Receiver<JsonValue>), RpcError>;
+
+/// Events of the socket, forwarded from its callbacks.
+enum Incoming {
+    Text(String),
+    Closed(String),
+}
+
+/// A request waiting for its response.
+enum Pending {
+    Request(oneshot::Sender<Result<JsonValue, RpcError>>),
+    Subscription {
+        unsubscribe_method: String,
+        send_back: oneshot::Sender<SubscribeResult>,
+    },
+}
+
+impl Pending {
+    /// Return the error to the client instead of a response.
+    fn fail(self, err: RpcError) {
+        match self {
+            Pending::Request(send_back) => {
+                let _ = send_back.send(Err(err));
+            }
+            Pending::Subscription { send_back, .. } => {
+                let _ = send_back.send(Err(err));
+            }
+        }
+    }
+}
+
+/// An active subscription, with the channel its notifications are forwarded to.
+struct ActiveSubscription {
+    notifs: mpsc::Sender<JsonValue>,
+    unsubscribe_method: String,
+}
+
+/// The task owning the socket, which sends the requests of the client and routes the
+/// responses and notifications back to it.
+struct Background {
+    socket: WebSocket,
+    next_id: u64,
+    pending: HashMap<u64, Pending>,
+    subscriptions: HashMap<SubscriptionId, ActiveSubscription>,
+    // the callbacks of the socket, which must live as long as it
+    _closures: (
+        Closure<dyn FnMut(MessageEvent)>,
+        Closure<dyn FnMut(CloseEvent)>,
+    ),
+}
+
+impl Background {
+    async fn run(
+        mut self,
+        mut from_front: mpsc::UnboundedReceiver<Message>,
+        mut closed: mpsc::Receiver<FrontToBack>,
+        mut incoming: mpsc::UnboundedReceiver<Incoming>,
+    ) {
+        let mut client_dropped = false;
+        loop {
+            select! {
+                message = from_front.next() => match message {
+                    Some(message) => self.send(message),
+                    None => client_dropped = true,
+                },
+                message = closed.next() => {
+                    if let Some(FrontToBack::SubscriptionClosed(id)) = message {
+                        self.unsubscribe(id);
+                    }
+                }
+                event = incoming.next() => match event {
+                    Some(Incoming::Text(text)) => self.receive(&text),
+                    Some(Incoming::Closed(reason)) => {
+                        log::warn!("Connection closed: {}", reason);
+                        break
+                    }
+                    None => break,
+                },
+            }
+            if client_dropped && self.pending.is_empty() && self.subscriptions.is_empty()
+            {
+                break
+            }
+        }
+        // fail the requests still waiting for a response, and end the subscriptions
+        for (_, pending) in self.pending.drain() {
+            pending.fail(connection_closed());
+        }
+        self.subscriptions.clear();
+        let _ = self.socket.close();
+    }
+
+    /// Send the request of the client, remembering where to send its response.
+    fn send(&mut self, message: Message) {
+        let id = self.next_id;
+        self.next_id += 1;
+        let (method, params, pending) = match message {
+            Message::Request {
+                method,
+                params,
+                send_back,
+            } => (method, params, Pending::Request(send_back)),
+            Message::Subscribe {
+                method,
+                params,
+                unsubscribe_method,
+                send_back,
+            } => {
+                (
+                    method,
+                    params,
+                    Pending::Subscription {
+                        unsubscribe_method,
+                        send_back,
+                    },
+                )
+            }
+        };
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        match self.socket.send_with_str(&request.to_string()) {
+            Ok(()) => {
+                self.pending.insert(id, pending);
+            }
+            Err(err) => pending.fail(js_error("Sending the request", err)),
+        }
+    }
+
+    /// Route a response or notification of the node to the client.
+    fn receive(&mut self, text: &str) {
+        let mut message: JsonValue = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(err) => {
+                log::warn!("Invalid message from the node: {}", err);
+                return
+            }
+        };
+        if let Some(id) = message.get("id").and_then(JsonValue::as_u64) {
+            let pending = match self.pending.remove(&id) {
+                Some(pending) => pending,
+                // e.g. the response to an unsubscribe request
+                None => return,
+            };
+            // the error response in full, like the native clients return it
+            let result = if message.get("error").is_some() {
+                Err(RpcError::Request(text.to_owned()))
+            } else {
+                Ok(message["result"].take())
+            };
+            match pending {
+                Pending::Request(send_back) => {
+                    let _ = send_back.send(result);
+                }
+                Pending::Subscription {
+                    unsubscribe_method,
+                    send_back,
+                } => {
+                    let subscribed = result.and_then(|result| {
+                        serde_json::from_value::<SubscriptionId>(result).map_err(|_| {
+                            RpcError::Custom("Invalid subscription id".into())
+                        })
+                    });
+                    let response = subscribed.map(|id| {
+                        let (notifs, notifs_rx) =
+                            mpsc::channel(MAX_NOTIFS_PER_SUBSCRIPTION);
+                        self.subscriptions.insert(
+                            id.clone(),
+                            ActiveSubscription {
+                                notifs,
+                                unsubscribe_method,
+                            },
+                        );
+                        (id, notifs_rx)
+                    });
+                    let _ = send_back.send(response);
+                }
+            }
+        } else if let Some(params) = message.get_mut("params") {
+            let id = match serde_json::from_value::<SubscriptionId>(
+                params["subscription"].take(),
+            ) {
+                Ok(id) => id,
+                Err(_) => return,
+            };
+            let delivered = match self.subscriptions.get_mut(&id) {
+                Some(subscription) => {
+                    subscription
+                        .notifs
+                        .try_send(params["result"].take())
+                        .is_ok()
+                }
+                None => return,
+            };
+            // the subscription was dropped, or doesn't keep up with its notifications
+            if !delivered {
+                self.unsubscribe(id);
+            }
+        }
+    }
+
+    /// Stop the subscription with the given id on the node.
+    fn unsubscribe(&mut self, id: SubscriptionId) {
+        if let Some(subscription) = self.subscriptions.remove(&id) {
+            let request = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": self.next_id,
+                "method": subscription.unsubscribe_method,
+                "params": [id],
+            });
+            self.next_id += 1;
+            let _ = self.socket.send_with_str(&request.to_string());
+        }
+    }
+}
+
+fn connection_closed() -> RpcError {
+    RpcError::RestartNeeded("Browser WebSocket connection closed".into())
+}
+
+fn js_error(action: &str, err: wasm_bindgen::JsValue) -> RpcError {
+    RpcError::Custom(format!("{} failed: {:?}", action, err))
+}
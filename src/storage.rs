@@ -17,24 +17,43 @@
 //! For querying runtime storage.
 
 use codec::{
+    Compact,
     Decode,
     Encode,
 };
+use frame_metadata::StorageEntryType;
+use futures::{
+    future::{
+        self,
+        BoxFuture,
+        Either,
+    },
+    stream::FuturesOrdered,
+    StreamExt,
+};
 use sp_core::storage::{
     StorageChangeSet,
     StorageData,
     StorageKey,
 };
 pub use sp_runtime::traits::SignedExtension;
+use sp_runtime::SaturatedConversion;
 pub use sp_version::RuntimeVersion;
-use std::marker::PhantomData;
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+};
 
 use crate::{
     metadata::{
         Metadata,
         MetadataError,
     },
-    rpc::Rpc,
+    rpc::{
+        NumberOrHex,
+        Rpc,
+    },
+    subscription::StorageChangeSetSubscription,
     Config,
     Error,
     StorageHasher,
@@ -53,13 +72,20 @@ pub trait StorageEntry {
 }
 
 /// The prefix of the key to a [`StorageEntry`]
+#[derive(Clone)]
 pub struct StorageKeyPrefix(Vec<u8>);
 
 impl StorageKeyPrefix {
     /// Create the storage key prefix for a [`StorageEntry`]
     pub fn new<T: StorageEntry>() -> Self {
-        let mut bytes = sp_core::twox_128(T::PALLET.as_bytes()).to_vec();
-        bytes.extend(&sp_core::twox_128(T::STORAGE.as_bytes())[..]);
+        Self::from_names(T::PALLET, T::STORAGE)
+    }
+
+    /// Create the storage key prefix for the storage entry with the given pallet and storage
+    /// names.
+    pub fn from_names(pallet: &str, storage: &str) -> Self {
+        let mut bytes = sp_core::twox_128(pallet.as_bytes()).to_vec();
+        bytes.extend(&sp_core::twox_128(storage.as_bytes())[..]);
         Self(bytes)
     }
 
@@ -171,6 +197,67 @@ impl<'a, T: Config> StorageClient<'a, T> {
         self.rpc.storage(&key, hash).await
     }
 
+    /// Fetch the size in bytes of the raw encoded value under the raw storage key, e.g. to
+    /// estimate the bandwidth of fetching it. Returns `None` if there is no value.
+    pub async fn size(
+        &self,
+        key: StorageKey,
+        hash: Option<T::Hash>,
+    ) -> Result<Option<u64>, Error> {
+        self.rpc.storage_size(&key, hash).await
+    }
+
+    /// Fetch the hash of the block with the given number, to query storage at that block.
+    ///
+    /// Returns an error if there is no block with that number yet.
+    pub async fn block_hash(&self, number: T::BlockNumber) -> Result<T::Hash, Error> {
+        let block_number = NumberOrHex::Number(number.saturated_into::<u64>()).into();
+        self.rpc
+            .block_hash(Some(block_number))
+            .await?
+            .ok_or_else(|| format!("Block number {:?} not found", number).into())
+    }
+
+    /// Fetch the raw encoded values under multiple raw storage keys with a single RPC call.
+    ///
+    /// The values are returned in the same order as the keys.
+    pub async fn fetch_batch(
+        &self,
+        keys: Vec<StorageKey>,
+        hash: Option<T::Hash>,
+    ) -> Result<Vec<Option<StorageData>>, Error> {
+        let change_sets = self.rpc.query_storage_at(&keys, hash).await?;
+        let values = change_sets
+            .into_iter()
+            .flat_map(|change_set| change_set.changes)
+            .collect::<HashMap<_, _>>();
+        Ok(keys
+            .iter()
+            .map(|key| values.get(key).cloned().flatten())
+            .collect())
+    }
+
+    /// Fetch and decode the values under multiple storage keys with a single RPC call.
+    ///
+    /// Like [`Self::fetch_unhashed`] the keys are the final keys of the values, e.g. from
+    /// [`StorageEntryKey::final_key`], which are not hashed any further. The values are
+    /// returned in the same order as the keys.
+    pub async fn fetch_batch_unhashed<V: Decode>(
+        &self,
+        keys: Vec<StorageKey>,
+        hash: Option<T::Hash>,
+    ) -> Result<Vec<Option<V>>, Error> {
+        self.fetch_batch(keys, hash)
+            .await?
+            .into_iter()
+            .map(|data| {
+                data.map(|data| Decode::decode(&mut &data.0[..]))
+                    .transpose()
+                    .map_err(Into::into)
+            })
+            .collect()
+    }
+
     /// Fetch a StorageKey with an optional block hash.
     pub async fn fetch<F: StorageEntry>(
         &self,
@@ -199,6 +286,94 @@ impl<'a, T: Config> StorageClient<'a, T> {
         }
     }
 
+    /// Fetch a StorageKey with a `Vec` value, to decode its items one at a time instead of
+    /// decoding all of them into a `Vec` at once.
+    ///
+    /// The SCALE encoded value is still fetched and held in memory in full, since the node
+    /// returns storage values in one piece. Only the decoded items are not, which saves the
+    /// memory of items decoding into larger types than their encoding, e.g. with heap
+    /// allocations of their own.
+    pub async fn fetch_vec<F, E>(
+        &self,
+        store: &F,
+        hash: Option<T::Hash>,
+    ) -> Result<Option<VecDecoder<E>>, Error>
+    where
+        F: StorageEntry<Value = Vec<E>>,
+        E: Decode,
+    {
+        let key = store.key().final_key(StorageKeyPrefix::new::<F>());
+        match self.fetch_raw(key, hash).await? {
+            Some(data) => Ok(Some(VecDecoder::new(data.0)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch and decode the value of the storage entry with the given pallet and storage names
+    /// into a type chosen by the caller, falling back to the default value of the entry in
+    /// the metadata if there is no value.
+    ///
+    /// An escape hatch for entries without a typed accessor, e.g. because the value type is
+    /// only known at runtime.
+    pub async fn fetch_decode<V: Decode>(
+        &self,
+        pallet: &'static str,
+        storage: &'static str,
+        key: StorageEntryKey,
+        hash: Option<T::Hash>,
+    ) -> Result<V, Error> {
+        let key = key.final_key(StorageKeyPrefix::from_names(pallet, storage));
+        if let Some(value) = self.fetch_unhashed(key, hash).await? {
+            Ok(value)
+        } else {
+            let storage_metadata = self.metadata.pallet(pallet)?.storage(storage)?;
+            let default = Decode::decode(&mut &storage_metadata.default[..])
+                .map_err(MetadataError::DefaultError)?;
+            Ok(default)
+        }
+    }
+
+    /// Decode the key of a single key storage map from the final storage key of one of its
+    /// entries, e.g. as returned by [`KeyIter::next`], using the hasher from the metadata.
+    ///
+    /// Only keys hashed with a hasher which appends the key, i.e. `Blake2_128Concat`,
+    /// `Twox64Concat` or `Identity`, can be decoded.
+    pub fn decode_map_key<F: StorageEntry, K: Decode>(
+        &self,
+        key: &StorageKey,
+    ) -> Result<K, Error> {
+        let storage_metadata = self.metadata.pallet(F::PALLET)?.storage(F::STORAGE)?;
+        let hasher = match storage_metadata.ty {
+            StorageEntryType::Map { ref hashers, .. } if hashers.len() == 1 => {
+                &hashers[0]
+            }
+            _ => return Err(MetadataError::StorageTypeError.into()),
+        };
+        let hash_len = match hasher {
+            StorageHasher::Blake2_128Concat => 16,
+            StorageHasher::Twox64Concat => 8,
+            StorageHasher::Identity => 0,
+            _ => {
+                return Err(format!(
+                    "Storage map keys hashed with {:?} can't be decoded",
+                    hasher
+                )
+                .into())
+            }
+        };
+        let prefix = StorageKeyPrefix::new::<F>();
+        if !key.0.starts_with(&prefix.0) {
+            return Err(
+                format!("Not a storage key of {}::{}", F::PALLET, F::STORAGE).into(),
+            )
+        }
+        let hashed_key = &key.0[prefix.0.len()..];
+        if hashed_key.len() < hash_len {
+            return Err("Storage key is too short to contain the map key".into())
+        }
+        Ok(K::decode(&mut &hashed_key[hash_len..])?)
+    }
+
     /// Query historical storage entries
     pub async fn query_storage(
         &self,
@@ -209,6 +384,46 @@ impl<'a, T: Config> StorageClient<'a, T> {
         self.rpc.query_storage(keys, from, to).await
     }
 
+    /// Query the changes of the values under the given keys in the blocks with numbers from
+    /// `from` to `to` inclusive, with a single RPC call.
+    ///
+    /// Returns a change set for the first block and for every later block in which any of the
+    /// values changed, in block order.
+    pub async fn query_storage_range(
+        &self,
+        keys: Vec<StorageKey>,
+        from: T::BlockNumber,
+        to: T::BlockNumber,
+    ) -> Result<Vec<StorageChangeSet<T::Hash>>, Error> {
+        let from = self.block_hash(from).await?;
+        let to = self.block_hash(to).await?;
+        self.query_storage(keys, from, Some(to)).await
+    }
+
+    /// Subscribe to changes of the given raw storage keys in imported blocks.
+    ///
+    /// With a reconnecting client, the subscription is re-established after reconnecting.
+    pub async fn subscribe(
+        &self,
+        keys: Vec<StorageKey>,
+    ) -> Result<StorageChangeSetSubscription<T>, Error> {
+        self.rpc.subscribe_storage(keys).await
+    }
+
+    /// Subscribe to changes of the value of a storage entry in imported blocks.
+    pub async fn subscribe_entry<F: StorageEntry>(
+        &self,
+        store: &F,
+    ) -> Result<StorageEntrySubscription<T, F>, Error> {
+        let key = store.key().final_key(StorageKeyPrefix::new::<F>());
+        let subscription = self.subscribe(vec![key.clone()]).await?;
+        Ok(StorageEntrySubscription {
+            subscription,
+            key,
+            _marker: PhantomData,
+        })
+    }
+
     /// Fetch up to `count` keys for a storage map in lexicographic order.
     ///
     /// Supports pagination by passing a value to `start_key`.
@@ -226,10 +441,50 @@ impl<'a, T: Config> StorageClient<'a, T> {
         Ok(keys)
     }
 
+    /// Fetch up to `count` keys with the given prefix in lexicographic order, e.g. for a
+    /// storage map without a generated [`StorageEntry`].
+    ///
+    /// Supports pagination by passing a value to `start_key`.
+    pub async fn fetch_keys_raw(
+        &self,
+        prefix: StorageKey,
+        count: u32,
+        start_key: Option<StorageKey>,
+        hash: Option<T::Hash>,
+    ) -> Result<Vec<StorageKey>, Error> {
+        let prefix = StorageKeyPrefix(prefix.0);
+        let keys = self
+            .rpc
+            .storage_keys_paged(Some(prefix), count, start_key, hash)
+            .await?;
+        Ok(keys)
+    }
+
     /// Returns an iterator of key value pairs.
     pub async fn iter<F: StorageEntry>(
         &self,
         hash: Option<T::Hash>,
+    ) -> Result<KeyIter<'a, T, F>, Error> {
+        self.iter_with_prefix(StorageKeyPrefix::new::<F>(), hash)
+            .await
+    }
+
+    /// Returns an iterator of key value pairs for the entries of a map whose keys start with
+    /// the given partial keys, e.g. all the entries under a given first key of a double map.
+    pub async fn iter_prefix<F: StorageEntry>(
+        &self,
+        partial_keys: Vec<StorageMapKey>,
+        hash: Option<T::Hash>,
+    ) -> Result<KeyIter<'a, T, F>, Error> {
+        let key =
+            StorageEntryKey::Map(partial_keys).final_key(StorageKeyPrefix::new::<F>());
+        self.iter_with_prefix(StorageKeyPrefix(key.0), hash).await
+    }
+
+    async fn iter_with_prefix<F: StorageEntry>(
+        &self,
+        prefix: StorageKeyPrefix,
+        hash: Option<T::Hash>,
     ) -> Result<KeyIter<'a, T, F>, Error> {
         let hash = if let Some(hash) = hash {
             hash
@@ -241,57 +496,206 @@ impl<'a, T: Config> StorageClient<'a, T> {
         };
         Ok(KeyIter {
             client: self.clone(),
+            prefix,
             hash,
             count: self.iter_page_size,
             start_key: None,
             buffer: Default::default(),
+            max_pages_in_flight: 1,
+            keys_in_flight: None,
+            pages_in_flight: FuturesOrdered::new(),
+            keys_exhausted: false,
             _marker: PhantomData,
         })
     }
 }
 
+/// The key value pairs of a page of a map.
+type Page = Vec<(StorageKey, StorageData)>;
+
 /// Iterates over key value pairs in a map.
 pub struct KeyIter<'a, T: Config, F: StorageEntry> {
     client: StorageClient<'a, T>,
+    prefix: StorageKeyPrefix,
     _marker: PhantomData<F>,
     count: u32,
     hash: T::Hash,
     start_key: Option<StorageKey>,
-    buffer: Vec<(StorageKey, StorageData)>,
+    buffer: Page,
+    max_pages_in_flight: usize,
+    keys_in_flight: Option<BoxFuture<'a, Result<Vec<StorageKey>, Error>>>,
+    pages_in_flight: FuturesOrdered<BoxFuture<'a, Result<Page, Error>>>,
+    keys_exhausted: bool,
 }
 
 impl<'a, T: Config, F: StorageEntry> KeyIter<'a, T, F> {
+    /// Fetch the values of up to `pages` pages of the map concurrently, rather than one page
+    /// at a time, to speed up iterating over large maps.
+    ///
+    /// The keys of the pages are still fetched one page after another, since each page
+    /// starts after the last key of the previous one, but while the values of the previous
+    /// pages are fetched. Defaults to one page.
+    pub fn buffer(mut self, pages: usize) -> Self {
+        self.max_pages_in_flight = pages.max(1);
+        self
+    }
+
     /// Returns the next key value pair from a map.
     pub async fn next(&mut self) -> Result<Option<(StorageKey, F::Value)>, Error> {
         loop {
             if let Some((k, v)) = self.buffer.pop() {
                 return Ok(Some((k, Decode::decode(&mut &v.0[..])?)))
-            } else {
-                let keys = self
-                    .client
-                    .fetch_keys::<F>(self.count, self.start_key.take(), Some(self.hash))
-                    .await?;
-
-                if keys.is_empty() {
-                    return Ok(None)
+            }
+            if self.keys_in_flight.is_none()
+                && !self.keys_exhausted
+                && self.pages_in_flight.len() < self.max_pages_in_flight
+            {
+                self.keys_in_flight = Some(self.fetch_next_keys());
+            }
+
+            // wait for the keys of the next page and the values of the pages in flight
+            // together, so that both are fetched concurrently
+            let fetched = match self.keys_in_flight.as_mut() {
+                None => Either::Right(self.pages_in_flight.next().await),
+                Some(keys) if self.pages_in_flight.is_empty() => Either::Left(keys.await),
+                Some(keys) => {
+                    match future::select(keys, self.pages_in_flight.next()).await {
+                        Either::Left((keys, _)) => Either::Left(keys),
+                        Either::Right((page, _)) => Either::Right(page),
+                    }
+                }
+            };
+            match fetched {
+                Either::Left(keys) => {
+                    self.keys_in_flight = None;
+                    self.fetch_values(keys?);
                 }
+                Either::Right(Some(page)) => self.buffer = page?,
+                Either::Right(None) => return Ok(None),
+            }
+        }
+    }
+
+    /// Start fetching the keys of the next page.
+    fn fetch_next_keys(&mut self) -> BoxFuture<'a, Result<Vec<StorageKey>, Error>> {
+        let rpc = self.client.rpc;
+        let prefix = self.prefix.clone();
+        let count = self.count;
+        let start_key = self.start_key.take();
+        let hash = self.hash;
+        Box::pin(async move {
+            rpc.storage_keys_paged(Some(prefix), count, start_key, Some(hash))
+                .await
+        })
+    }
+
+    /// Start fetching the values of the keys of a page.
+    fn fetch_values(&mut self, keys: Vec<StorageKey>) {
+        if keys.is_empty() {
+            self.keys_exhausted = true;
+            return
+        }
+
+        self.start_key = keys.last().cloned();
 
-                self.start_key = keys.last().cloned();
-
-                let change_sets = self
-                    .client
-                    .rpc
-                    .query_storage_at(&keys, Some(self.hash))
-                    .await?;
-                for change_set in change_sets {
-                    for (k, v) in change_set.changes {
-                        if let Some(v) = v {
-                            self.buffer.push((k, v));
-                        }
+        let rpc = self.client.rpc;
+        let hash = self.hash;
+        self.pages_in_flight.push(Box::pin(async move {
+            let change_sets = rpc.query_storage_at(&keys, Some(hash)).await?;
+            let mut page = Vec::with_capacity(keys.len());
+            for change_set in change_sets {
+                for (k, v) in change_set.changes {
+                    if let Some(v) = v {
+                        page.push((k, v));
                     }
                 }
-                debug_assert_eq!(self.buffer.len(), keys.len());
+            }
+            debug_assert_eq!(page.len(), keys.len());
+            Ok(page)
+        }));
+    }
+}
+
+/// Subscription to the value of a single storage entry.
+pub struct StorageEntrySubscription<T: Config, F: StorageEntry> {
+    subscription: StorageChangeSetSubscription<T>,
+    key: StorageKey,
+    _marker: PhantomData<F>,
+}
+
+impl<T: Config, F: StorageEntry> StorageEntrySubscription<T, F> {
+    /// Returns the hash of the next block in which the entry changed, along with its new
+    /// value, which is `None` if the entry was removed.
+    pub async fn next(&mut self) -> Result<Option<(T::Hash, Option<F::Value>)>, Error> {
+        while let Some(change_set) = self.subscription.next().await? {
+            let change = change_set
+                .changes
+                .into_iter()
+                .find(|(key, _)| key == &self.key);
+            if let Some((_, data)) = change {
+                let value = data
+                    .map(|data| Decode::decode(&mut &data.0[..]))
+                    .transpose()?;
+                return Ok(Some((change_set.block, value)))
             }
         }
+        Ok(None)
+    }
+}
+
+/// Decodes the items of a SCALE encoded `Vec` lazily, one at a time.
+///
+/// The encoded `Vec` is held in full, only the decoded items are produced one at a time.
+pub struct VecDecoder<E> {
+    data: Vec<u8>,
+    offset: usize,
+    remaining: u32,
+    _marker: PhantomData<E>,
+}
+
+impl<E: Decode> VecDecoder<E> {
+    /// Creates a new decoder of the SCALE encoded `Vec`, reading only its length prefix.
+    pub fn new(data: Vec<u8>) -> Result<Self, Error> {
+        let mut input = &data[..];
+        let Compact(remaining) = Compact::<u32>::decode(&mut input)?;
+        let offset = data.len() - input.len();
+        Ok(Self {
+            data,
+            offset,
+            remaining,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the number of items left to decode.
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+}
+
+impl<E: Decode> Iterator for VecDecoder<E> {
+    type Item = Result<E, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let mut input = &self.data[self.offset..];
+        match E::decode(&mut input) {
+            Ok(item) => {
+                self.offset = self.data.len() - input.len();
+                self.remaining -= 1;
+                Some(Ok(item))
+            }
+            Err(err) => {
+                // stop at the first item which fails to decode
+                self.remaining = 0;
+                Some(Err(err.into()))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
     }
 }
@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
+use codec::Decode;
 use jsonrpsee_types::{
     DeserializeOwned,
     Subscription,
@@ -26,7 +27,10 @@ use sp_core::{
     twox_128,
 };
 use sp_runtime::traits::Header;
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    marker::PhantomData,
+};
 
 use crate::{
     error::Error,
@@ -43,13 +47,16 @@ use crate::{
 
 /// Event subscription simplifies filtering a storage change set stream for
 /// events of interest.
+///
+/// Dropping the subscription unsubscribes from the node.
 pub struct EventSubscription<'a, T: Config> {
     subscription: EventStorageSubscription<T>,
     decoder: &'a EventsDecoder<T>,
     block: Option<T::Hash>,
     extrinsic: Option<usize>,
     event: Option<(&'static str, &'static str)>,
-    events: VecDeque<RawEvent>,
+    events_filter: Option<fn(&RawEvent) -> bool>,
+    events: VecDeque<(T::Hash, RawEvent)>,
     finished: bool,
 }
 
@@ -65,6 +72,7 @@ impl<'a, T: Config> EventSubscription<'a, T> {
             block: None,
             extrinsic: None,
             event: None,
+            events_filter: None,
             events: Default::default(),
             finished: false,
         }
@@ -86,8 +94,40 @@ impl<'a, T: Config> EventSubscription<'a, T> {
         self.event = Some((E::PALLET, E::EVENT));
     }
 
+    /// Filters events by any of the types of the tuple `F`, decoding the matching events.
+    pub fn filter_events<F: EventFilter>(
+        mut self,
+    ) -> FilteredEventSubscription<'a, T, F> {
+        self.events_filter = Some(F::is_match);
+        FilteredEventSubscription {
+            subscription: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Decodes the events into the outer event enum `E` of the runtime, e.g. the `Event` enum
+    /// generated by the `subxt` macro, so that they can be matched against the typed events
+    /// of the pallets.
+    ///
+    /// Only the events passing the filters of this subscription are decoded.
+    pub fn runtime_events<E: Decode>(self) -> RuntimeEventSubscription<'a, T, E> {
+        RuntimeEventSubscription {
+            subscription: self,
+            _marker: PhantomData,
+        }
+    }
+
     /// Gets the next event.
     pub async fn next(&mut self) -> Option<Result<RawEvent, Error>> {
+        self.next_with_block()
+            .await
+            .map(|result| result.map(|(_block, event)| event))
+    }
+
+    /// Gets the next event, along with the hash of the block containing it.
+    pub async fn next_with_block(
+        &mut self,
+    ) -> Option<Result<(T::Hash, RawEvent), Error>> {
         loop {
             if let Some(event) = self.events.pop_front() {
                 return Some(Ok(event))
@@ -126,7 +166,12 @@ impl<'a, T: Config> EventSubscription<'a, T> {
                                     continue
                                 }
                             }
-                            self.events.push_back(event);
+                            if let Some(is_match) = self.events_filter {
+                                if !is_match(&event) {
+                                    continue
+                                }
+                            }
+                            self.events.push_back((change_set.block, event));
                         }
                     }
                 }
@@ -135,6 +180,95 @@ impl<'a, T: Config> EventSubscription<'a, T> {
     }
 }
 
+/// A tuple of event types to filter an [`EventSubscription`] by.
+///
+/// Matching events are decoded into a tuple of options, in which only the option of the
+/// matching event type is set.
+pub trait EventFilter {
+    /// The decoded event.
+    type Output;
+    /// Returns true if the raw event is of one of the event types.
+    fn is_match(event: &RawEvent) -> bool;
+    /// Decodes a matching raw event.
+    fn decode(event: &RawEvent) -> Result<Self::Output, codec::Error>;
+}
+
+macro_rules! impl_event_filter {
+    ($($event:ident),+) => {
+        impl<$($event: Event),+> EventFilter for ($($event,)+) {
+            type Output = ($(Option<$event>,)+);
+
+            fn is_match(event: &RawEvent) -> bool {
+                $( $event::is_event(&event.pallet, &event.variant) )||+
+            }
+
+            fn decode(event: &RawEvent) -> Result<Self::Output, codec::Error> {
+                Ok(($(
+                    if $event::is_event(&event.pallet, &event.variant) {
+                        Some(<$event as codec::Decode>::decode(&mut &event.data[..])?)
+                    } else {
+                        None
+                    },
+                )+))
+            }
+        }
+    };
+}
+
+impl_event_filter!(A);
+impl_event_filter!(A, B);
+impl_event_filter!(A, B, C);
+impl_event_filter!(A, B, C, D);
+impl_event_filter!(A, B, C, D, E);
+impl_event_filter!(A, B, C, D, E, F);
+
+/// Event subscription which only returns events of the types of the tuple `F`, decoded.
+pub struct FilteredEventSubscription<'a, T: Config, F: EventFilter> {
+    subscription: EventSubscription<'a, T>,
+    _marker: PhantomData<F>,
+}
+
+impl<'a, T: Config, F: EventFilter> FilteredEventSubscription<'a, T, F> {
+    /// Gets the next matching event.
+    pub async fn next(&mut self) -> Option<Result<F::Output, Error>> {
+        let event = match self.subscription.next().await? {
+            Ok(event) => event,
+            Err(error) => return Some(Err(error)),
+        };
+        Some(F::decode(&event).map_err(Into::into))
+    }
+}
+
+/// Event subscription which returns the events decoded into the outer event enum `E` of the
+/// runtime.
+pub struct RuntimeEventSubscription<'a, T: Config, E: Decode> {
+    subscription: EventSubscription<'a, T>,
+    _marker: PhantomData<E>,
+}
+
+impl<'a, T: Config, E: Decode> RuntimeEventSubscription<'a, T, E> {
+    /// Gets the next event.
+    pub async fn next(&mut self) -> Option<Result<E, Error>> {
+        self.next_with_block()
+            .await
+            .map(|result| result.map(|(_block, event)| event))
+    }
+
+    /// Gets the next event, along with the hash of the block containing it.
+    pub async fn next_with_block(&mut self) -> Option<Result<(T::Hash, E), Error>> {
+        let (block, event) = match self.subscription.next_with_block().await? {
+            Ok(event) => event,
+            Err(error) => return Some(Err(error)),
+        };
+        Some(
+            event
+                .as_runtime_event()
+                .map(|event| (block, event))
+                .map_err(Into::into),
+        )
+    }
+}
+
 pub(crate) struct SystemEvents(StorageKey);
 
 impl SystemEvents {
@@ -190,10 +324,126 @@ impl<T: Config> FinalizedEventStorageSubscription<T> {
     }
 }
 
+/// Subscription to the changes of storage keys in imported blocks.
+///
+/// With a reconnecting rpc client, the subscription subscribes again when the connection to
+/// the node is re-established, and first returns the values of the keys at every block
+/// imported while it was disconnected, so that no block is missed.
+pub struct StorageChangeSetSubscription<T: Config> {
+    rpc: Rpc<T>,
+    keys: Vec<StorageKey>,
+    subscription: Subscription<StorageChangeSet<T::Hash>>,
+    last_block: Option<T::Hash>,
+    resubscribed: bool,
+    missed: VecDeque<StorageChangeSet<T::Hash>>,
+}
+
+impl<T: Config> StorageChangeSetSubscription<T> {
+    /// Creates a new storage change set subscription to the given keys.
+    pub fn new(
+        rpc: Rpc<T>,
+        keys: Vec<StorageKey>,
+        subscription: Subscription<StorageChangeSet<T::Hash>>,
+    ) -> Self {
+        Self {
+            rpc,
+            keys,
+            subscription,
+            last_block: None,
+            resubscribed: false,
+            missed: Default::default(),
+        }
+    }
+
+    /// Gets the next change_set.
+    pub async fn next(&mut self) -> Result<Option<StorageChangeSet<T::Hash>>, Error> {
+        loop {
+            if let Some(change_set) = self.missed.pop_front() {
+                self.last_block = Some(change_set.block);
+                return Ok(Some(change_set))
+            }
+            match self.subscription.next().await {
+                Ok(Some(change_set)) if self.resubscribed => {
+                    self.resubscribed = false;
+                    self.missed = self.missed_change_sets(change_set).await?;
+                }
+                Ok(Some(change_set)) => {
+                    self.last_block = Some(change_set.block);
+                    return Ok(Some(change_set))
+                }
+                // the subscription terminates when the connection is dropped, subscribing
+                // again re-establishes the connection
+                Ok(None) | Err(_) if self.rpc.client.is_reconnecting() => {
+                    log::warn!("Storage subscription terminated, subscribing again");
+                    self.subscription =
+                        self.rpc.subscribe_storage_changes(&self.keys).await?;
+                    self.resubscribed = true;
+                }
+                result => return result.map_err(Into::into),
+            }
+        }
+    }
+
+    /// Returns the change sets of the blocks after the last returned block, up to and
+    /// including the first change set received after subscribing again.
+    async fn missed_change_sets(
+        &self,
+        next: StorageChangeSet<T::Hash>,
+    ) -> Result<VecDeque<StorageChangeSet<T::Hash>>, Error> {
+        let mut missed = VecDeque::new();
+        let last_block = match self.last_block {
+            // the current values are sent when subscribing, which have already been
+            // returned if no block has been imported since
+            Some(last_block) if last_block == next.block => return Ok(missed),
+            Some(last_block) => last_block,
+            None => {
+                missed.push_back(next);
+                return Ok(missed)
+            }
+        };
+        let last_number = match self.rpc.header(Some(last_block)).await? {
+            Some(header) => *header.number(),
+            None => {
+                missed.push_back(next);
+                return Ok(missed)
+            }
+        };
+        let mut parent = match self.rpc.header(Some(next.block)).await? {
+            Some(header) => *header.parent_hash(),
+            None => last_block,
+        };
+        missed.push_front(next);
+        while parent != last_block {
+            let header = match self.rpc.header(Some(parent)).await? {
+                Some(header) => header,
+                None => break,
+            };
+            // the last returned block has been retracted, so the chain is only walked
+            // back to its height
+            if *header.number() <= last_number {
+                break
+            }
+            for change_set in self
+                .rpc
+                .query_storage_at(&self.keys, Some(parent))
+                .await?
+                .into_iter()
+                .rev()
+            {
+                missed.push_front(change_set);
+            }
+            parent = *header.parent_hash();
+        }
+        Ok(missed)
+    }
+}
+
 /// Wrapper over imported and finalized event subscriptions.
 pub enum EventStorageSubscription<T: Config> {
     /// Events that are InBlock
     Imported(Subscription<StorageChangeSet<T::Hash>>),
+    /// Events that are InBlock, subscribing again after reconnecting
+    Reconnecting(StorageChangeSetSubscription<T>),
     /// Events that are Finalized
     Finalized(FinalizedEventStorageSubscription<T>),
 }
@@ -206,6 +456,12 @@ impl<T: Config> EventStorageSubscription<T> {
                 read_subscription_response("StorageChangeSetSubscription", event_sub)
                     .await
             }
+            Self::Reconnecting(event_sub) => {
+                event_sub.next().await.unwrap_or_else(|e| {
+                    log::error!("Event subscription failed: {:?} dropping", e);
+                    None
+                })
+            }
             Self::Finalized(event_sub) => event_sub.next().await,
         }
     }
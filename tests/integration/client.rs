@@ -15,16 +15,71 @@
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    runtime::node_runtime::system,
+    runtime::node_runtime::{
+        session,
+        system,
+        DefaultConfig,
+        RuntimeApi,
+    },
+    test_context,
     test_node_process,
     test_node_process_with,
+    TestProxy,
 };
 
-use sp_core::storage::{
-    well_known_keys,
-    StorageKey,
+use assert_matches::assert_matches;
+use codec::{
+    Decode,
+    Encode,
+};
+use frame_metadata::{
+    RuntimeMetadata,
+    RuntimeMetadataPrefixed,
+    META_RESERVED,
+};
+use sp_core::{
+    crypto::AccountId32,
+    sr25519,
+    storage::{
+        well_known_keys,
+        StorageKey,
+    },
+    Pair,
 };
 use sp_keyring::AccountKeyring;
+use sp_runtime::traits::Header as _;
+use std::{
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use subxt::{
+    extrinsic::PairSigner,
+    storage::{
+        RuntimeVersion,
+        StorageKeyPrefix,
+    },
+    Client,
+    ClientBuilder,
+    Error,
+    EventSubscription,
+    ListOrValue,
+    Metadata,
+    ReconnectPolicy,
+    RpcClient,
+    RpcTransport,
+    StorageEntry,
+    SystemProperties,
+    VecDecoder,
+};
 
 #[async_std::test]
 async fn insert_key() {
@@ -95,6 +150,83 @@ async fn chain_subscribe_finalized_blocks() {
     blocks.next().await.unwrap();
 }
 
+#[async_std::test]
+async fn chain_dropped_subscriptions_are_unsubscribed() {
+    let cxt = test_context().await;
+    let proxy = TestProxy::start(
+        cxt.node_proc
+            .ws_url()
+            .trim_start_matches("ws://")
+            .to_string(),
+    );
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_url(proxy.ws_url())
+        .build()
+        .await
+        .unwrap();
+
+    let blocks = client.rpc().subscribe_blocks().await.unwrap();
+    let events = client.rpc().subscribe_events().await.unwrap();
+    drop((blocks, events));
+
+    // the unsubscribe calls are sent in the background
+    let sent = |method: &str| {
+        proxy
+            .sent_messages()
+            .iter()
+            .any(|message| message.contains(method))
+    };
+    let start = Instant::now();
+    while !(sent("chain_unsubscribeNewHeads") && sent("state_unsubscribeStorage")) {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "Dropped subscriptions were not unsubscribed"
+        );
+        futures_timer::Delay::new(Duration::from_millis(10)).await;
+    }
+}
+
+#[async_std::test]
+async fn chain_subscribe_blocks_increasing_numbers() {
+    let node_process = test_node_process().await;
+    let client = node_process.client();
+    let mut blocks = client.rpc().subscribe_blocks().await.unwrap();
+
+    let first = blocks.next().await.unwrap().unwrap();
+    let second = blocks.next().await.unwrap().unwrap();
+    let third = blocks.next().await.unwrap().unwrap();
+    assert!(first.number < second.number);
+    assert!(second.number < third.number);
+    assert_eq!(third.parent_hash, second.hash());
+}
+
+#[async_std::test]
+async fn chain_subscribe_finalized_blocks_increasing_numbers() {
+    let node_process = test_node_process().await;
+    let client = node_process.client();
+    let mut blocks = client.rpc().subscribe_finalized_blocks().await.unwrap();
+
+    let first = blocks.next().await.unwrap().unwrap();
+    let second = blocks.next().await.unwrap().unwrap();
+    assert!(first.number < second.number);
+}
+
+#[async_std::test]
+async fn chain_block_author() {
+    let node_process = test_node_process().await;
+    let client = node_process.client();
+    let mut blocks = client.rpc().subscribe_blocks().await.unwrap();
+    let header = blocks.next().await.unwrap().unwrap();
+
+    let author = client.block_author(&header).await.unwrap();
+    // the dev node validates with the stash account of alice
+    let alice_stash: AccountId32 = sr25519::Pair::from_string("//Alice//stash", None)
+        .unwrap()
+        .public()
+        .into();
+    assert_eq!(author, Some(alice_stash));
+}
+
 #[async_std::test]
 async fn fetch_keys() {
     let node_process = test_node_process().await;
@@ -122,3 +254,754 @@ async fn test_iter() {
     }
     assert_eq!(i, 13);
 }
+
+#[async_std::test]
+async fn test_iter_buffered_pages() {
+    env_logger::try_init().ok();
+    let node_process = test_node_process().await;
+    // small pages, so that iterating over the accounts takes several pages
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_url(node_process.ws_url())
+        .set_page_size(2)
+        .build()
+        .await
+        .unwrap();
+    let storage = client.storage();
+
+    let mut iterated = Vec::new();
+    for pages in [1, 3, 4] {
+        let mut iter = storage
+            .iter::<system::storage::Account>(None)
+            .await
+            .unwrap()
+            .buffer(pages);
+        let mut accounts = Vec::new();
+        while let Some((key, account)) = iter.next().await.unwrap() {
+            accounts.push((key, account.encode()));
+        }
+        iterated.push(accounts);
+    }
+
+    // every account once
+    let mut keys = iterated[0]
+        .iter()
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+    assert_eq!(keys.len(), 13);
+    assert_eq!(iterated[0].len(), 13);
+    // and the same accounts in the same order with pages in flight
+    assert_eq!(iterated[1], iterated[0]);
+    assert_eq!(iterated[2], iterated[0]);
+}
+
+#[test]
+fn test_decode_large_vec_incrementally() {
+    let items = (0..1_000_000u32).collect::<Vec<_>>();
+    let mut decoder = VecDecoder::<u32>::new(items.encode()).unwrap();
+    assert_eq!(decoder.remaining(), 1_000_000);
+
+    for expected in 0..10 {
+        assert_eq!(decoder.next().unwrap().unwrap(), expected);
+    }
+    assert_eq!(decoder.remaining(), 999_990);
+    let sum = decoder.try_fold(0u64, |sum, item| item.map(|item| sum + item as u64));
+    assert_eq!(
+        sum.unwrap(),
+        items[10..].iter().map(|item| *item as u64).sum::<u64>()
+    );
+
+    // a truncated encoding fails at the missing item
+    let mut truncated = items[..3].to_vec().encode();
+    truncated.pop();
+    let decoded = VecDecoder::<u32>::new(truncated)
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(decoded.len(), 3);
+    assert!(decoded[2].is_err());
+}
+
+#[async_std::test]
+async fn test_fetch_vec_session_validators() {
+    let cxt = test_context().await;
+    let validators = cxt
+        .client()
+        .storage()
+        .fetch_vec(&session::storage::Validators, None)
+        .await
+        .unwrap()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let expected = cxt.api.storage().session().validators(None).await.unwrap();
+    assert_eq!(validators, expected);
+}
+
+#[async_std::test]
+async fn test_iter_includes_new_accounts() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let new_accounts = (0..3)
+        .map(|_| sr25519::Pair::generate().0.public().into())
+        .collect::<Vec<AccountId32>>();
+    for account in &new_accounts {
+        cxt.api
+            .tx()
+            .balances()
+            .transfer(account.clone(), 100_000_000_000_000_000)
+            .sign_and_submit_then_watch(&alice)
+            .await
+            .unwrap();
+    }
+
+    let mut iter = cxt.api.storage().system().account_iter(None).await.unwrap();
+    let mut keys = Vec::new();
+    while let Some((key, _account)) = iter.next().await.unwrap() {
+        keys.push(key);
+    }
+
+    for account in new_accounts {
+        let expected_key = system::storage::Account(account)
+            .key()
+            .final_key(StorageKeyPrefix::new::<system::storage::Account>());
+        assert!(keys.contains(&expected_key));
+    }
+}
+
+#[async_std::test]
+async fn test_fetch_batch() {
+    let cxt = test_context().await;
+    let accounts: Vec<AccountId32> = vec![
+        AccountKeyring::Alice.to_account_id(),
+        AccountKeyring::Bob.to_account_id(),
+        sr25519::Pair::generate().0.public().into(),
+    ];
+    let keys = accounts
+        .iter()
+        .map(|account| cxt.api.storage().system().account_key(account.clone()))
+        .collect::<Vec<_>>();
+
+    let batch = cxt
+        .client()
+        .storage()
+        .fetch_batch(keys.clone(), None)
+        .await
+        .unwrap();
+    assert_eq!(batch.len(), keys.len());
+    for (key, value) in keys.iter().zip(&batch) {
+        let individual = cxt
+            .client()
+            .storage()
+            .fetch_raw(key.clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(value, &individual);
+    }
+    assert!(batch[2].is_none());
+
+    let decoded = cxt
+        .client()
+        .storage()
+        .fetch_batch_unhashed::<<system::storage::Account as StorageEntry>::Value>(
+            keys, None,
+        )
+        .await
+        .unwrap();
+    assert!(decoded[2].is_none());
+    for (account, value) in accounts.into_iter().zip(decoded).take(2) {
+        let individual = cxt
+            .api
+            .storage()
+            .system()
+            .account(account, None)
+            .await
+            .unwrap();
+        assert_eq!(value.unwrap().encode(), individual.encode());
+    }
+}
+
+#[async_std::test]
+async fn test_runtime_version() {
+    let cxt = test_context().await;
+    let client = cxt.client();
+
+    let runtime_version = client.rpc().runtime_version(None).await.unwrap();
+    let version_constant = client
+        .metadata()
+        .pallet("System")
+        .unwrap()
+        .constant("Version")
+        .unwrap();
+    let metadata_version =
+        RuntimeVersion::decode(&mut &version_constant.value[..]).unwrap();
+    assert_eq!(runtime_version.spec_version, metadata_version.spec_version);
+    assert_eq!(
+        runtime_version.transaction_version,
+        metadata_version.transaction_version
+    );
+    assert_eq!(client.runtime_version(), &runtime_version);
+
+    let mut subscription = client.rpc().subscribe_runtime_version().await.unwrap();
+    let subscribed_version = subscription.next().await.unwrap().unwrap();
+    assert_eq!(subscribed_version, runtime_version);
+}
+
+#[async_std::test]
+async fn test_state_call_core_version() {
+    let cxt = test_context().await;
+    let rpc = cxt.client().rpc();
+
+    let bytes = rpc.state_call("Core_version", &[], None).await.unwrap();
+    let version = RuntimeVersion::decode(&mut &bytes[..]).unwrap();
+    assert_eq!(&version, cxt.client().runtime_version());
+
+    let decoded: RuntimeVersion = rpc
+        .state_call_decoded("Core_version", &(), None)
+        .await
+        .unwrap();
+    assert_eq!(decoded, version);
+
+    // the nonce of Alice through the runtime api, rather than the rpc of the node
+    let alice = AccountKeyring::Alice.to_account_id();
+    let nonce: u32 = rpc
+        .state_call_decoded("AccountNonceApi_account_nonce", &(alice.clone(),), None)
+        .await
+        .unwrap();
+    let account = cxt
+        .api
+        .storage()
+        .system()
+        .account(alice, None)
+        .await
+        .unwrap();
+    assert_eq!(nonce, account.nonce);
+}
+
+#[async_std::test]
+async fn test_system_health_and_sync_state() {
+    let cxt = test_context().await;
+    let rpc = cxt.client().rpc();
+
+    let health = rpc.system_health().await.unwrap();
+    assert!(!health.is_syncing);
+
+    let header = rpc.header(None).await.unwrap().unwrap();
+    let sync_state = rpc.system_sync_state().await.unwrap();
+    assert!(sync_state.current_block >= header.number as u64);
+}
+
+#[async_std::test]
+async fn test_system_chain_and_properties() {
+    let cxt = test_context().await;
+    let rpc = cxt.client().rpc();
+
+    assert_eq!(rpc.system_chain().await.unwrap(), "Development");
+    assert!(!rpc.system_name().await.unwrap().is_empty());
+    assert!(!rpc.system_version().await.unwrap().is_empty());
+
+    let properties = rpc.system_properties().await.unwrap();
+    assert_eq!(&properties, cxt.client().properties());
+    assert_eq!(properties.ss58_format, Some(42));
+    assert_matches!(properties.token_decimals, Some(ListOrValue::Value(_)));
+    assert_matches!(
+        properties.token_symbol,
+        Some(ListOrValue::Value(symbol)) if !symbol.is_empty()
+    );
+}
+
+#[test]
+fn test_system_properties_of_several_tokens() {
+    let properties: SystemProperties = serde_json::from_str(
+        r#"{"ss58Format":8,"tokenDecimals":[12,12],"tokenSymbol":["KAR","KUSD"]}"#,
+    )
+    .unwrap();
+    assert_eq!(properties.ss58_format, Some(8));
+    assert_eq!(
+        properties.token_decimals,
+        Some(ListOrValue::List(vec![12, 12]))
+    );
+    assert_eq!(
+        properties.token_symbol,
+        Some(ListOrValue::List(vec![
+            "KAR".to_string(),
+            "KUSD".to_string()
+        ]))
+    );
+}
+
+#[async_std::test]
+async fn test_custom_rpc_request() {
+    let cxt = test_context().await;
+    let rpc = cxt.client().rpc();
+
+    let runtime_version: RuntimeVersion =
+        rpc.request("state_getRuntimeVersion", ()).await.unwrap();
+    assert_eq!(&runtime_version, cxt.client().runtime_version());
+
+    let genesis_hash = rpc.genesis_hash().await.unwrap();
+    let header: Option<<DefaultConfig as subxt::Config>::Header> = rpc
+        .request("chain_getHeader", (genesis_hash,))
+        .await
+        .unwrap();
+    assert_eq!(header.unwrap().hash(), genesis_hash);
+}
+
+#[async_std::test]
+async fn test_genesis_hash() {
+    let cxt = test_context().await;
+    let client = cxt.client();
+
+    let block_zero_hash = client.rpc().block_hash(Some(0u32.into())).await.unwrap();
+    assert_eq!(block_zero_hash, Some(*client.genesis()));
+    assert_eq!(
+        client.rpc().genesis_hash().await.unwrap(),
+        *client.genesis()
+    );
+}
+
+#[async_std::test]
+async fn test_cloned_clients_share_connection() {
+    let cxt = test_context().await;
+    let alice = AccountKeyring::Alice.to_account_id();
+
+    let tasks = (0..5).map(|_| {
+        let api: RuntimeApi<DefaultConfig> = cxt.client().clone().to_runtime_api();
+        let alice = alice.clone();
+        async_std::task::spawn(async move {
+            api.storage().system().account(alice, None).await
+        })
+    });
+    for result in futures::future::join_all(tasks).await {
+        assert!(result.unwrap().data.free > 0);
+    }
+
+    let client = cxt.client().clone();
+    match (&client.rpc().client, &cxt.client().rpc().client) {
+        (RpcClient::WebSocket(clone), RpcClient::WebSocket(original)) => {
+            assert!(Arc::ptr_eq(clone, original))
+        }
+        _ => panic!("expected WebSocket clients"),
+    }
+}
+
+/// Transport forwarding requests to the node, except for reads of the total issuance.
+struct MockTransport {
+    node: RpcClient,
+    total_issuance: u128,
+    methods: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl RpcTransport for MockTransport {
+    async fn request(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value, Error> {
+        self.methods.lock().unwrap().push(method.to_owned());
+        let key =
+            StorageKeyPrefix::from_names("Balances", "TotalIssuance").to_storage_key();
+        if method == "state_getStorage"
+            && params.first() == Some(&serde_json::to_value(key)?)
+        {
+            let value = sp_core::Bytes(self.total_issuance.encode());
+            return Ok(serde_json::to_value(value)?)
+        }
+        self.node.request(method, &params).await
+    }
+}
+
+#[async_std::test]
+async fn test_custom_transport() {
+    let node_process = test_node_process().await;
+    let transport = Arc::new(MockTransport {
+        node: RpcClient::try_from_url(node_process.ws_url())
+            .await
+            .unwrap(),
+        total_issuance: 42,
+        methods: Default::default(),
+    });
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_client(transport.clone() as Arc<dyn RpcTransport>)
+        .build()
+        .await
+        .unwrap();
+    let api = client.to_runtime_api::<RuntimeApi<DefaultConfig>>();
+
+    let total_issuance = api.storage().balances().total_issuance(None).await.unwrap();
+    assert_eq!(total_issuance, 42);
+    assert!(transport
+        .methods
+        .lock()
+        .unwrap()
+        .contains(&"state_getStorage".to_owned()));
+
+    // subscriptions need a connection to the node
+    assert!(api.client.rpc().subscribe_blocks().await.is_err());
+}
+
+#[async_std::test]
+async fn test_fetch_metadata_bytes() {
+    let node_process = test_node_process().await;
+    let client = node_process.client();
+    let bytes = client.rpc().metadata_bytes().await.unwrap();
+    let metadata: Metadata = RuntimeMetadataPrefixed::decode(&mut &bytes[..])
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert!(metadata.pallet("Balances").is_ok());
+}
+
+#[async_std::test]
+async fn test_update_metadata() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let mut cxt = test_context().await;
+    let metadata_pre = cxt.client().metadata().runtime_metadata().encode();
+
+    cxt.api.client.update_metadata().await.unwrap();
+    assert_eq!(
+        cxt.client().metadata().runtime_metadata().encode(),
+        metadata_pre
+    );
+
+    // events and storage are still decoded with the swapped in metadata
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    assert!(result
+        .find_event::<system::events::ExtrinsicSuccess>()
+        .unwrap()
+        .is_some());
+    cxt.api
+        .storage()
+        .system()
+        .account(AccountKeyring::Bob.to_account_id(), None)
+        .await
+        .unwrap();
+}
+
+/// Transport forwarding requests to the node, except for fetching the runtime version while
+/// `fail_runtime_version` is set.
+struct FailingRuntimeVersionTransport {
+    node: RpcClient,
+    fail_runtime_version: AtomicBool,
+}
+
+#[async_trait::async_trait]
+impl RpcTransport for FailingRuntimeVersionTransport {
+    async fn request(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value, Error> {
+        if method == "state_getRuntimeVersion"
+            && self.fail_runtime_version.load(Ordering::SeqCst)
+        {
+            return Err("Runtime version unavailable".into())
+        }
+        self.node.request(method, &params).await
+    }
+}
+
+#[async_std::test]
+async fn test_update_metadata_keeps_both_if_runtime_version_fails() {
+    let node_process = test_node_process().await;
+    let transport = Arc::new(FailingRuntimeVersionTransport {
+        node: RpcClient::try_from_url(node_process.ws_url())
+            .await
+            .unwrap(),
+        fail_runtime_version: AtomicBool::new(false),
+    });
+    let mut client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_client(transport.clone() as Arc<dyn RpcTransport>)
+        .build()
+        .await
+        .unwrap();
+
+    // swap in metadata without the Democracy pallet, so that an update is visible
+    let mut runtime_metadata = client.metadata().runtime_metadata().clone();
+    runtime_metadata
+        .pallets
+        .retain(|pallet| pallet.name != "Democracy");
+    let metadata: Metadata =
+        RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(runtime_metadata))
+            .try_into()
+            .unwrap();
+    client.set_metadata(metadata);
+    let runtime_version = client.runtime_version().clone();
+
+    transport.fail_runtime_version.store(true, Ordering::SeqCst);
+    assert!(client.update_metadata().await.is_err());
+    assert!(client.metadata().pallet("Democracy").is_err());
+    assert_eq!(client.runtime_version(), &runtime_version);
+
+    transport
+        .fail_runtime_version
+        .store(false, Ordering::SeqCst);
+    client.update_metadata().await.unwrap();
+    assert!(client.metadata().pallet("Democracy").is_ok());
+    assert_eq!(client.runtime_version(), &runtime_version);
+}
+
+#[async_std::test]
+async fn test_constants_read_from_swapped_metadata() {
+    let mut cxt = test_context().await;
+    let existential_deposit = cxt
+        .api
+        .constants()
+        .balances()
+        .existential_deposit()
+        .unwrap();
+
+    let mut runtime_metadata = cxt.client().metadata().runtime_metadata().clone();
+    let constant = runtime_metadata
+        .pallets
+        .iter_mut()
+        .find(|pallet| pallet.name == "Balances")
+        .unwrap()
+        .constants
+        .iter_mut()
+        .find(|constant| constant.name == "ExistentialDeposit")
+        .unwrap();
+    constant.value = (existential_deposit * 2).encode();
+    let metadata: Metadata =
+        RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(runtime_metadata))
+            .try_into()
+            .unwrap();
+
+    cxt.api.client.set_metadata(metadata);
+    assert_eq!(
+        cxt.api
+            .constants()
+            .balances()
+            .existential_deposit()
+            .unwrap(),
+        existential_deposit * 2
+    );
+}
+
+#[async_std::test]
+async fn test_validate_call_against_mismatched_metadata() {
+    let mut cxt = test_context().await;
+    let bob = AccountKeyring::Bob.to_account_id();
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .validate()
+        .unwrap();
+
+    // give the balances pallet the calls of the assets pallet, which has a `transfer` call
+    // with different arguments
+    let mut runtime_metadata = cxt.client().metadata().runtime_metadata().clone();
+    let assets_calls = runtime_metadata
+        .pallets
+        .iter()
+        .find(|pallet| pallet.name == "Assets")
+        .unwrap()
+        .calls
+        .clone();
+    runtime_metadata
+        .pallets
+        .iter_mut()
+        .find(|pallet| pallet.name == "Balances")
+        .unwrap()
+        .calls = assets_calls;
+    let metadata: Metadata =
+        RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(runtime_metadata))
+            .try_into()
+            .unwrap();
+
+    cxt.api.client.set_metadata(metadata);
+    let result = cxt.api.tx().balances().transfer(bob, 10_000).validate();
+    assert_matches!(result, Err(Error::MetadataMismatch("Balances", "transfer")));
+}
+
+#[async_std::test]
+async fn test_http_client() {
+    let cxt = test_context().await;
+    let http_api: RuntimeApi<DefaultConfig> = ClientBuilder::new()
+        .set_url(cxt.node_proc.http_url())
+        .build()
+        .await
+        .unwrap()
+        .to_runtime_api();
+
+    let alice = AccountKeyring::Alice.to_account_id();
+    let over_ws = cxt
+        .api
+        .storage()
+        .system()
+        .account(alice.clone(), None)
+        .await
+        .unwrap();
+    let over_http = http_api
+        .storage()
+        .system()
+        .account(alice, None)
+        .await
+        .unwrap();
+    assert_eq!(over_ws.encode(), over_http.encode());
+
+    // subscriptions are only supported over websockets
+    assert!(http_api.client.rpc().subscribe_blocks().await.is_err());
+}
+
+#[async_std::test]
+async fn test_reconnect_event_subscription() {
+    let cxt = test_context().await;
+    let proxy = TestProxy::start(
+        cxt.node_proc
+            .ws_url()
+            .trim_start_matches("ws://")
+            .to_string(),
+    );
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_url(proxy.ws_url())
+        .set_reconnect_policy(ReconnectPolicy::default())
+        .build()
+        .await
+        .unwrap();
+
+    let subscription = client.rpc().subscribe_events().await.unwrap();
+    let mut events = EventSubscription::new(subscription, client.events_decoder());
+    // every block contains at least the timestamp inherent
+    assert!(events.next().await.unwrap().is_ok());
+
+    proxy.drop_connections();
+    // events of the current block may still be buffered
+    while client.rpc().client.reconnections() == 0 {
+        assert!(events.next().await.unwrap().is_ok());
+    }
+    // the subscription continues after reconnecting
+    assert!(events.next().await.unwrap().is_ok());
+    assert_eq!(client.rpc().client.reconnections(), 1);
+}
+
+#[async_std::test]
+async fn test_reconnect_storage_subscription_returns_missed_blocks() {
+    let cxt = test_context().await;
+    let proxy = TestProxy::start(
+        cxt.node_proc
+            .ws_url()
+            .trim_start_matches("ws://")
+            .to_string(),
+    );
+    let api: RuntimeApi<DefaultConfig> = ClientBuilder::new()
+        .set_url(proxy.ws_url())
+        .set_reconnect_policy(ReconnectPolicy::default())
+        .build()
+        .await
+        .unwrap()
+        .to_runtime_api();
+
+    let mut numbers = api.storage().system().number_subscribe().await.unwrap();
+    let (_, number) = numbers.next().await.unwrap().unwrap();
+    let mut number = number.unwrap();
+
+    proxy.drop_connections();
+    // the subscription only reconnects when polled, so wait for blocks to be imported
+    // while it is disconnected
+    let mut blocks = cxt.client().rpc().subscribe_blocks().await.unwrap();
+    let mut head = blocks.next().await.unwrap().unwrap();
+    while *head.number() < number + 2 {
+        head = blocks.next().await.unwrap().unwrap();
+    }
+
+    // the value at every block is returned, including the ones imported while
+    // disconnected
+    while number < *head.number() {
+        let (_, next) = numbers.next().await.unwrap().unwrap();
+        assert_eq!(next, Some(number + 1));
+        number += 1;
+    }
+    assert_eq!(api.client.rpc().client.reconnections(), 1);
+}
+
+#[async_std::test]
+async fn test_failover_to_live_endpoint() {
+    let cxt = test_context().await;
+    // nothing listens on the first endpoint
+    let dead_url = "ws://127.0.0.1:1".to_string();
+    let live_url = cxt.node_proc.ws_url().to_string();
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_urls(vec![dead_url, live_url.clone()])
+        .set_reconnect_policy(ReconnectPolicy {
+            initial_backoff: Duration::from_millis(10),
+            ..Default::default()
+        })
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(client.rpc().client.active_url().await, Some(live_url));
+    client.rpc().finalized_head().await.unwrap();
+}
+
+#[async_std::test]
+async fn test_failover_from_unresponsive_endpoint() {
+    let cxt = test_context().await;
+    let proxy = TestProxy::start(
+        cxt.node_proc
+            .ws_url()
+            .trim_start_matches("ws://")
+            .to_string(),
+    );
+    let live_url = cxt.node_proc.ws_url().to_string();
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_urls(vec![proxy.ws_url(), live_url.clone()])
+        .set_reconnect_policy(ReconnectPolicy {
+            request_timeout: Duration::from_millis(500),
+            ..Default::default()
+        })
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(client.rpc().client.active_url().await, Some(proxy.ws_url()));
+
+    proxy.set_stalled(true);
+    for _ in 0..2 {
+        assert_matches!(client.rpc().finalized_head().await, Err(Error::Rpc(_)));
+    }
+    // the endpoint is failed over from after three consecutive timeouts, and the third
+    // request is retried on the next endpoint
+    client.rpc().finalized_head().await.unwrap();
+    assert_eq!(client.rpc().client.active_url().await, Some(live_url));
+}
+
+#[async_std::test]
+async fn test_failover_from_unresponsive_endpoint_on_subscribe() {
+    let cxt = test_context().await;
+    let proxy = TestProxy::start(
+        cxt.node_proc
+            .ws_url()
+            .trim_start_matches("ws://")
+            .to_string(),
+    );
+    let live_url = cxt.node_proc.ws_url().to_string();
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_urls(vec![proxy.ws_url(), live_url.clone()])
+        .set_reconnect_policy(ReconnectPolicy {
+            request_timeout: Duration::from_millis(500),
+            ..Default::default()
+        })
+        .build()
+        .await
+        .unwrap();
+
+    proxy.set_stalled(true);
+    // timed out subscriptions count towards the failover like requests
+    for _ in 0..2 {
+        assert_matches!(client.rpc().subscribe_blocks().await, Err(Error::Rpc(_)));
+    }
+    let mut blocks = client.rpc().subscribe_blocks().await.unwrap();
+    assert_eq!(client.rpc().client.active_url().await, Some(live_url));
+    blocks.next().await.unwrap();
+}
@@ -0,0 +1,180 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+    encode_event_records,
+    fixture_metadata,
+    node_runtime::{
+        balances,
+        DefaultConfig,
+        Event,
+    },
+    METADATA_BYTES,
+};
+use assert_matches::assert_matches;
+use codec::Encode;
+use sp_keyring::AccountKeyring;
+use subxt::{
+    EventRecord,
+    EventsDecoder,
+    Phase,
+    Raw,
+};
+
+#[test]
+fn decode_transfer_with_types_from_metadata() {
+    let decoder = EventsDecoder::<DefaultConfig>::new(fixture_metadata());
+
+    let alice = AccountKeyring::Alice.to_account_id();
+    let bob = AccountKeyring::Bob.to_account_id();
+    let event = Event::Balances(balances::Event::Transfer(
+        alice.clone(),
+        bob.clone(),
+        10_000,
+    ));
+    // a single event record, emitted by the first extrinsic
+    let input = encode_event_records(vec![(Phase::ApplyExtrinsic(0), event)]);
+
+    let events = decoder.decode_events(&mut &input[..]).unwrap();
+    assert_eq!(events.len(), 1);
+    let (phase, event) = events.into_iter().next().unwrap();
+    let record = EventRecord { phase, event };
+    assert_eq!(record.extrinsic_index(), Some(0));
+    assert_eq!(
+        record.as_event::<balances::events::Transfer>().unwrap(),
+        Some(balances::events::Transfer(alice, bob, 10_000))
+    );
+}
+
+#[test]
+fn decode_archived_transfer_with_decoder_from_metadata_bytes() {
+    let decoder =
+        EventsDecoder::<DefaultConfig>::from_metadata_bytes(METADATA_BYTES).unwrap();
+
+    let alice = AccountKeyring::Alice.to_account_id();
+    let bob = AccountKeyring::Bob.to_account_id();
+    // the `System::Events` storage of a block with a transfer in its second extrinsic
+    let archived = encode_event_records(vec![(
+        Phase::ApplyExtrinsic(1),
+        Event::Balances(balances::Event::Transfer(
+            alice.clone(),
+            bob.clone(),
+            10_000,
+        )),
+    )]);
+
+    let events = decoder.decode_events(&mut &archived[..]).unwrap();
+    assert_eq!(events.len(), 1);
+    let (phase, event) = events.into_iter().next().unwrap();
+    assert_eq!(phase, Phase::ApplyExtrinsic(1));
+    let record = EventRecord { phase, event };
+    assert_eq!(
+        record.as_event::<balances::events::Transfer>().unwrap(),
+        Some(balances::events::Transfer(alice, bob, 10_000))
+    );
+
+    assert!(EventsDecoder::<DefaultConfig>::from_metadata_bytes(&archived).is_err());
+}
+
+#[test]
+fn decode_events_iter_matches_decode_events() {
+    let decoder = EventsDecoder::<DefaultConfig>::new(fixture_metadata());
+
+    let alice = AccountKeyring::Alice.to_account_id();
+    let bob = AccountKeyring::Bob.to_account_id();
+    let count = 5_000u32;
+    let input = encode_event_records((0..count).map(|i| {
+        let event = Event::Balances(balances::Event::Transfer(
+            alice.clone(),
+            bob.clone(),
+            i as u128,
+        ));
+        (Phase::ApplyExtrinsic(i), event)
+    }));
+
+    let eager = decoder.decode_events(&mut &input[..]).unwrap();
+    let lazy = decoder
+        .decode_events_iter(&input)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(eager.len(), count as usize);
+    assert_eq!(lazy.len(), eager.len());
+
+    for (i, ((phase, event), record)) in eager.into_iter().zip(lazy).enumerate() {
+        assert_eq!(record.phase, phase);
+        assert_eq!(record.extrinsic_index(), Some(i as u32));
+        match (event, record.event) {
+            (Raw::Event(event), Raw::Event(lazy_event)) => {
+                assert_eq!(lazy_event.pallet, event.pallet);
+                assert_eq!(lazy_event.variant, event.variant);
+                assert_eq!(lazy_event.data, event.data);
+            }
+            _ => panic!("expected events"),
+        }
+    }
+}
+
+#[test]
+fn metadata_pallet_call_and_event_indices() {
+    let metadata = fixture_metadata();
+
+    let pallet = metadata.pallet("Balances").unwrap();
+    assert_eq!(pallet.index(), 6);
+    assert_eq!(pallet.call("transfer").unwrap().index(), 0);
+    assert!(pallet
+        .call("transfer")
+        .unwrap()
+        .docs()
+        .iter()
+        .any(|line| line.contains("Transfer some liquid free balance")));
+    assert!(!pallet.storage("TotalIssuance").unwrap().docs.is_empty());
+
+    let transfer = balances::Event::Transfer(
+        AccountKeyring::Alice.to_account_id(),
+        AccountKeyring::Bob.to_account_id(),
+        10_000,
+    );
+    let event = metadata
+        .event(pallet.index(), transfer.encode()[0])
+        .unwrap();
+    assert_eq!(event.event(), "Transfer");
+    assert_eq!(event.index(), 2);
+}
+
+#[test]
+fn metadata_type_registry_contains_call_type() {
+    let metadata = fixture_metadata();
+
+    let call_type = metadata
+        .types()
+        .types()
+        .iter()
+        .find(|ty| ty.ty().path().segments() == ["pallet_balances", "pallet", "Call"])
+        .unwrap();
+    let pallet = metadata
+        .runtime_metadata()
+        .pallets
+        .iter()
+        .find(|pallet| pallet.name == "Balances")
+        .unwrap();
+    assert_eq!(call_type.id(), pallet.calls.as_ref().unwrap().ty.id());
+    assert_matches!(
+        call_type.ty().type_def(),
+        scale_info::TypeDef::Variant(variant)
+            if variant.variants().iter().any(|variant| variant.name() == "transfer")
+    );
+}
@@ -0,0 +1,235 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+    fixture_metadata,
+    node_runtime::{
+        balances,
+        DefaultConfig,
+    },
+};
+use codec::{
+    Decode,
+    Encode,
+};
+use core::fmt::Debug;
+use scale_info::TypeInfo;
+use sp_core::{
+    blake2_256,
+    H256,
+};
+use sp_keyring::AccountKeyring;
+use sp_runtime::{
+    generic::Era,
+    traits::{
+        SignedExtension,
+        Verify,
+    },
+    transaction_validity::TransactionValidityError,
+};
+use subxt::{
+    extrinsic::{
+        create_signed_offline,
+        ExtrinsicParams,
+        OfflineParams,
+        PairSigner,
+    },
+    Config,
+    DefaultExtra,
+    ExtrinsicExtraData,
+    SignedExtra,
+};
+
+/// Config of the test node types, with a signed extension the test node doesn't have.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct CustomExtraConfig;
+
+impl Config for CustomExtraConfig {
+    type Index = u32;
+    type BlockNumber = u32;
+    type Hash = sp_core::H256;
+    type Hashing = sp_runtime::traits::BlakeTwo256;
+    type AccountId = sp_runtime::AccountId32;
+    type Address = sp_runtime::MultiAddress<Self::AccountId, u32>;
+    type Header = sp_runtime::generic::Header<Self::BlockNumber, Self::Hashing>;
+    type Signature = sp_runtime::MultiSignature;
+    type Extrinsic = sp_runtime::OpaqueExtrinsic;
+}
+
+impl ExtrinsicExtraData<CustomExtraConfig> for CustomExtraConfig {
+    type Extra = CustomExtra<CustomExtraConfig>;
+}
+
+/// An extension the default set doesn't have, with extra data and an `additional_signed`
+/// of its own.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, TypeInfo)]
+struct CheckChainTag(u8);
+
+const CHAIN_TAG: [u8; 4] = *b"test";
+
+impl SignedExtension for CheckChainTag {
+    const IDENTIFIER: &'static str = "CheckChainTag";
+    type AccountId = u64;
+    type Call = ();
+    type AdditionalSigned = [u8; 4];
+    type Pre = ();
+    fn additional_signed(
+        &self,
+    ) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        Ok(CHAIN_TAG)
+    }
+}
+
+/// The default extensions, followed by [`CheckChainTag`].
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+struct CustomExtra<T: Config> {
+    default: DefaultExtra<T>,
+}
+
+impl<T: Config + Clone + Debug + Eq + Send + Sync> SignedExtra<T> for CustomExtra<T> {
+    type Extra = (<DefaultExtra<T> as SignedExtra<T>>::Extra, CheckChainTag);
+
+    fn new(
+        spec_version: u32,
+        tx_version: u32,
+        nonce: T::Index,
+        genesis_hash: T::Hash,
+        params: ExtrinsicParams<T>,
+    ) -> Self {
+        CustomExtra {
+            default: DefaultExtra::new(
+                spec_version,
+                tx_version,
+                nonce,
+                genesis_hash,
+                params,
+            ),
+        }
+    }
+
+    fn extra(&self) -> Self::Extra {
+        (self.default.extra(), CheckChainTag(7))
+    }
+}
+
+impl<T: Config + Clone + Debug + Eq + Send + Sync> SignedExtension for CustomExtra<T> {
+    const IDENTIFIER: &'static str = "CustomExtra";
+    type AccountId = T::AccountId;
+    type Call = ();
+    type AdditionalSigned =
+        <<Self as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned;
+    type Pre = ();
+
+    fn additional_signed(
+        &self,
+    ) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        self.extra().additional_signed()
+    }
+}
+
+#[async_std::test]
+async fn tx_signed_with_custom_extra() {
+    let metadata = fixture_metadata();
+    let alice = PairSigner::<CustomExtraConfig, _>::new(AccountKeyring::Alice.pair());
+    let genesis_hash = H256::repeat_byte(1);
+    let params = OfflineParams {
+        spec_version: 100,
+        tx_version: 2,
+        genesis_hash,
+        nonce: 0,
+        extrinsic_params: Default::default(),
+    };
+
+    let call: balances::calls::Transfer = balances::calls::Transfer {
+        dest: AccountKeyring::Bob.to_account_id().into(),
+        value: 10_000,
+    };
+    let extrinsic = create_signed_offline(&metadata, &call, &alice, params)
+        .await
+        .unwrap();
+
+    // the extra data of the extension follows the one of the default extensions
+    let default_extra = DefaultExtra::<CustomExtraConfig>::new(
+        100,
+        2,
+        0,
+        genesis_hash,
+        ExtrinsicParams::default(),
+    );
+    let mut expected_extra = default_extra.extra().encode();
+    expected_extra.push(7);
+    let encoded_call = metadata.encode_call(&call).unwrap();
+    let encoded = extrinsic.encode();
+    let extra_and_call = [&expected_extra[..], &encoded_call.0[..]].concat();
+    assert!(encoded.ends_with(&extra_and_call));
+
+    // and its additional data is signed along with the one of the default extensions
+    let (_, signature, _) = extrinsic.signature.as_ref().unwrap();
+    let additional_signed = default_extra.additional_signed().unwrap();
+    let unsigned_tag = [
+        &encoded_call.0[..],
+        &expected_extra[..],
+        &additional_signed.encode()[..],
+    ]
+    .concat();
+    let signed = [&unsigned_tag[..], &CHAIN_TAG[..]].concat();
+    // payloads longer than 256 bytes are signed hashed
+    let hashed = |payload: Vec<u8>| {
+        if payload.len() > 256 {
+            blake2_256(&payload).to_vec()
+        } else {
+            payload
+        }
+    };
+    let alice_id = AccountKeyring::Alice.to_account_id();
+    assert!(signature.verify(&hashed(signed)[..], &alice_id));
+    assert!(!signature.verify(&hashed(unsigned_tag)[..], &alice_id));
+}
+
+#[test]
+fn default_extra_additional_signed_includes_hashes() {
+    let genesis_hash = H256::repeat_byte(1);
+    let extra = DefaultExtra::<DefaultConfig>::new(
+        100,
+        2,
+        0,
+        genesis_hash,
+        ExtrinsicParams::default(),
+    );
+    // an immortal era is counted from the genesis block
+    assert_eq!(
+        extra.additional_signed().unwrap().encode(),
+        (100u32, 2u32, genesis_hash, genesis_hash).encode()
+    );
+
+    let checkpoint = H256::repeat_byte(2);
+    let extra = DefaultExtra::<DefaultConfig>::new(
+        100,
+        2,
+        0,
+        genesis_hash,
+        ExtrinsicParams {
+            era: Era::mortal(64, 10),
+            era_checkpoint: Some(checkpoint),
+            tip: 0,
+        },
+    );
+    assert_eq!(
+        extra.additional_signed().unwrap().encode(),
+        (100u32, 2u32, genesis_hash, checkpoint).encode()
+    );
+}
@@ -16,35 +16,74 @@
 
 use crate::{
     node_runtime::{
+        self,
         balances,
         runtime_types,
         system,
+        Call,
         DefaultConfig,
+        Event,
     },
     test_context,
+    TestProxy,
 };
-use codec::Decode;
+use assert_matches::assert_matches;
+use codec::{
+    Decode,
+    Encode,
+};
+use futures::StreamExt;
 use sp_core::{
     sr25519::Pair,
     Pair as _,
 };
 use sp_keyring::AccountKeyring;
+use sp_runtime::{
+    traits::{
+        BlakeTwo256,
+        Hash as _,
+    },
+    MultiAddress,
+};
 use subxt::{
     extrinsic::{
+        create_signed_offline,
+        ChargeTransactionPayment,
+        OfflineParams,
         PairSigner,
         Signer,
     },
+    storage::{
+        StorageEntryKey,
+        StorageKeyPrefix,
+    },
+    AccountIndex,
+    Address20,
+    Address32,
+    Client,
+    ClientBuilder,
+    DefaultExtra,
+    DispatchClass,
+    Encoded,
     Error,
     EventSubscription,
     PalletError,
+    Pays,
+    Phase,
+    Raw,
+    RawAddress,
     RuntimeError,
+    SignedExtrinsic,
+    TransactionError,
+    TransactionManager,
+    TransactionStatus,
 };
 
 #[async_std::test]
 async fn tx_basic_transfer() {
     let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
     let bob = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Bob.pair());
-    let bob_address = bob.account_id().clone().into();
+    let bob_address = bob.account_id().clone();
     let cxt = test_context().await;
     let api = &cxt.api;
 
@@ -101,6 +140,249 @@ async fn tx_basic_transfer() {
     assert_eq!(bob_pre.data.free + 10_000, bob_post.data.free);
 }
 
+/// Like `tx_basic_transfer`, on tokio rather than async-std. The test utilities don't depend
+/// on either runtime, so nothing of the test runs on another executor.
+#[tokio::test]
+async fn tx_basic_transfer_on_tokio() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let api = &cxt.api;
+
+    let bob_pre = api
+        .storage()
+        .system()
+        .account(bob.clone(), None)
+        .await
+        .unwrap();
+
+    let result = api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    let event = result
+        .find_event::<balances::events::Transfer>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        event,
+        balances::events::Transfer(alice.account_id().clone(), bob.clone(), 10_000)
+    );
+
+    let bob_post = api.storage().system().account(bob, None).await.unwrap();
+    assert_eq!(bob_pre.data.free + 10_000, bob_post.data.free);
+}
+
+#[async_std::test]
+async fn tx_transfer_to_account_index() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Bob.pair());
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .indices()
+        .claim(7)
+        .sign_and_submit_then_watch(&bob)
+        .await
+        .unwrap();
+    let assigned = result
+        .find_event::<node_runtime::indices::events::IndexAssigned>()
+        .unwrap();
+    assert_eq!(
+        assigned,
+        Some(node_runtime::indices::events::IndexAssigned(
+            bob.account_id().clone(),
+            7
+        ))
+    );
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountIndex(7), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    let transfer = result.find_event::<balances::events::Transfer>().unwrap();
+    assert_eq!(
+        transfer,
+        Some(balances::events::Transfer(
+            alice.account_id().clone(),
+            bob.account_id().clone(),
+            10_000
+        ))
+    );
+
+    // the lookup of the node only supports ids and indices
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(Address32(bob.account_id().clone().into()), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn address_constructors_convert_into_multi_address() {
+    type Address = <DefaultConfig as subxt::Config>::Address;
+
+    assert_eq!(Address::from(AccountIndex(7)), MultiAddress::Index(7));
+    assert_eq!(
+        Address::from(RawAddress(vec![1, 2, 3])),
+        MultiAddress::Raw(vec![1, 2, 3])
+    );
+    assert_eq!(
+        Address::from(Address32([1; 32])),
+        MultiAddress::Address32([1; 32])
+    );
+    assert_eq!(
+        Address::from(Address20([2; 20])),
+        MultiAddress::Address20([2; 20])
+    );
+}
+
+#[async_std::test]
+async fn tx_transfer_keep_alive() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer_keep_alive(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let transfer = result.find_event::<balances::events::Transfer>().unwrap();
+    assert_eq!(
+        transfer,
+        Some(balances::events::Transfer(
+            alice.account_id().clone(),
+            bob,
+            10_000
+        ))
+    );
+}
+
+#[async_std::test]
+async fn tx_transfer_all() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let hans = PairSigner::<DefaultConfig, _>::new(Pair::generate().0);
+    let cxt = test_context().await;
+
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(hans.account_id().clone(), 100_000_000_000_000_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer_all(alice.account_id().clone(), false)
+        .sign_and_submit_then_watch(&hans)
+        .await
+        .unwrap();
+
+    let transfer = result
+        .find_event::<balances::events::Transfer>()
+        .unwrap()
+        .expect("Failed to find Transfer");
+    assert_eq!(&transfer.0, hans.account_id());
+    assert_eq!(&transfer.1, alice.account_id());
+    // everything but the fee is transferred, and the emptied account is reaped
+    assert!(transfer.2 < 100_000_000_000_000_000);
+    let killed = result
+        .find_event::<system::events::KilledAccount>()
+        .unwrap();
+    assert_eq!(
+        killed,
+        Some(system::events::KilledAccount(hans.account_id().clone()))
+    );
+
+    let hans_post = cxt
+        .api
+        .storage()
+        .system()
+        .account(hans.account_id().clone(), None)
+        .await
+        .unwrap();
+    assert_eq!(hans_post.data.free, 0);
+}
+
+#[async_std::test]
+async fn tx_transfer_to_ss58_address() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let bob = cxt
+        .client()
+        .account_id_from_ss58("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty")
+        .unwrap();
+    assert_eq!(bob, AccountKeyring::Bob.to_account_id());
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    let transfer = result.find_event::<balances::events::Transfer>().unwrap();
+    assert_eq!(
+        transfer,
+        Some(balances::events::Transfer(
+            alice.account_id().clone(),
+            bob,
+            10_000
+        ))
+    );
+
+    // Bob's address on Polkadot
+    let polkadot_bob = cxt
+        .client()
+        .account_id_from_ss58("14E5nqKAp3oAJcmzgZhUD2RcptBeUBScxKHgJKU4HPNcKVf3");
+    assert_matches!(polkadot_bob, Err(Error::WrongNetwork(42, 0)));
+}
+
+#[async_std::test]
+async fn ss58_address_round_trip_with_custom_format() {
+    let cxt = test_context().await;
+    let bob = AccountKeyring::Bob.to_account_id();
+
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_url(cxt.node_proc.ws_url())
+        .set_ss58_format(1284)
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(client.ss58_format(), 1284);
+
+    let address = client.account_id_to_ss58(&bob);
+    assert_ne!(address, cxt.client().account_id_to_ss58(&bob));
+    assert_eq!(client.account_id_from_ss58(&address).unwrap(), bob);
+
+    // the address of Bob on the dev chain is for another network
+    let dev_bob =
+        client.account_id_from_ss58("5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty");
+    assert_matches!(dev_bob, Err(Error::WrongNetwork(1284, 42)));
+}
+
 #[async_std::test]
 async fn storage_total_issuance() {
     let cxt = test_context().await;
@@ -114,6 +396,25 @@ async fn storage_total_issuance() {
     assert_ne!(total_issuance, 0);
 }
 
+#[async_std::test]
+async fn storage_fetch_decode_total_issuance() {
+    let cxt = test_context().await;
+    let total_issuance = cxt
+        .api
+        .storage()
+        .balances()
+        .total_issuance(None)
+        .await
+        .unwrap();
+    let decoded = cxt
+        .client()
+        .storage()
+        .fetch_decode::<u128>("Balances", "TotalIssuance", StorageEntryKey::Plain, None)
+        .await
+        .unwrap();
+    assert_eq!(decoded, total_issuance);
+}
+
 #[async_std::test]
 async fn storage_balance_lock() -> Result<(), subxt::Error> {
     let bob = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Bob.pair());
@@ -125,7 +426,7 @@ async fn storage_balance_lock() -> Result<(), subxt::Error> {
         .tx()
         .staking()
         .bond(
-            charlie.into(),
+            charlie,
             100_000_000_000_000,
             runtime_types::pallet_staking::RewardDestination::Stash,
         )
@@ -158,9 +459,9 @@ async fn storage_balance_lock() -> Result<(), subxt::Error> {
 async fn transfer_error() {
     env_logger::try_init().ok();
     let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
-    let alice_addr = alice.account_id().clone().into();
+    let alice_addr = alice.account_id().clone();
     let hans = PairSigner::<DefaultConfig, _>::new(Pair::generate().0);
-    let hans_address = hans.account_id().clone().into();
+    let hans_address = hans.account_id().clone();
     let cxt = test_context().await;
 
     cxt.api
@@ -184,6 +485,8 @@ async fn transfer_error() {
             pallet: "Balances".into(),
             error: "InsufficientBalance".into(),
             description: vec!["Balance too low to send value".to_string()],
+            pallet_index: cxt.client().metadata().pallet("Balances").unwrap().index(),
+            error_index: balances::Error::InsufficientBalance.encode()[0],
         };
         assert_eq!(error, error2);
     } else {
@@ -191,12 +494,194 @@ async fn transfer_error() {
     }
 }
 
+#[async_std::test]
+async fn tx_transfer_retry_on_stale_nonce() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .nonce(0)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    // the nonce was used by the first transfer
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .nonce(0)
+        .sign_and_submit_then_watch(&alice)
+        .await;
+    assert_matches!(result, Err(Error::Rpc(_)));
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .nonce(0)
+        .sign_and_submit_then_watch_retry(&alice, 1)
+        .await
+        .unwrap();
+    assert!(result
+        .find_event::<balances::events::Transfer>()
+        .unwrap()
+        .is_some());
+}
+
+#[async_std::test]
+async fn tx_transfer_retry_returns_dispatch_error() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let hans = PairSigner::<DefaultConfig, _>::new(Pair::generate().0);
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(hans.account_id().clone(), u128::MAX)
+        .sign_and_submit_then_watch_retry(&alice, 3)
+        .await;
+    assert_matches!(result, Err(Error::Runtime(RuntimeError::Module(error))) if error.error == "InsufficientBalance");
+}
+
+#[async_std::test]
+async fn tx_transfer_retry_not_on_other_rejections() {
+    let hans = PairSigner::<DefaultConfig, _>::new(Pair::generate().0);
+    let cxt = test_context().await;
+    let proxy = TestProxy::start(
+        cxt.node_proc
+            .ws_url()
+            .trim_start_matches("ws://")
+            .to_string(),
+    );
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_url(proxy.ws_url())
+        .build()
+        .await
+        .unwrap();
+    let api = client.to_runtime_api::<node_runtime::RuntimeApi<DefaultConfig>>();
+
+    // the pool rejects the transfer, since hans can't pay the fees
+    let result = api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Alice.to_account_id(), 10_000)
+        .sign_and_submit_then_watch_retry(&hans, 3)
+        .await;
+    assert_matches!(result, Err(Error::Rpc(_)));
+    let submissions = proxy
+        .sent_messages()
+        .iter()
+        .filter(|message| message.contains("author_submitAndWatchExtrinsic"))
+        .count();
+    assert_eq!(submissions, 1);
+}
+
+#[async_std::test]
+async fn tx_transfer_retry_not_on_usurped() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let proxy = TestProxy::start(
+        cxt.node_proc
+            .ws_url()
+            .trim_start_matches("ws://")
+            .to_string(),
+    );
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_url(proxy.ws_url())
+        .build()
+        .await
+        .unwrap();
+    let api = client.to_runtime_api::<node_runtime::RuntimeApi<DefaultConfig>>();
+    let nonce = cxt
+        .client()
+        .rpc()
+        .account_nonce(alice.account_id())
+        .await
+        .unwrap();
+
+    let usurped = api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .nonce(nonce)
+        .sign_and_submit_then_watch_retry(&alice, 3);
+    // once the transfer is in the pool, replace it by one with the same nonce and a higher
+    // priority
+    let usurping = async {
+        while cxt
+            .client()
+            .rpc()
+            .pending_extrinsics()
+            .await
+            .unwrap()
+            .is_empty()
+        {
+            futures_timer::Delay::new(std::time::Duration::from_millis(10)).await;
+        }
+        cxt.api
+            .tx()
+            .balances()
+            .transfer(bob.clone(), 20_000)
+            .nonce(nonce)
+            .tip(1_000_000_000)
+            .sign_and_submit(&alice)
+            .await
+    };
+    let (result, usurping) = futures::join!(usurped, usurping);
+    let usurping = usurping.unwrap();
+    assert_matches!(
+        result,
+        Err(Error::Transaction(TransactionError::Usurped(hash))) if hash == usurping.as_bytes()
+    );
+    let submissions = proxy
+        .sent_messages()
+        .iter()
+        .filter(|message| message.contains("author_submitAndWatchExtrinsic"))
+        .count();
+    assert_eq!(submissions, 1);
+}
+
+#[async_std::test]
+async fn transfer_error_as_runtime_error() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let hans = PairSigner::<DefaultConfig, _>::new(Pair::generate().0);
+    let cxt = test_context().await;
+
+    let res = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(hans.account_id().clone(), u128::MAX)
+        .sign_and_submit_then_watch(&alice)
+        .await;
+
+    if let Err(Error::Runtime(RuntimeError::Module(error))) = res {
+        assert_matches!(
+            error.as_runtime_error(),
+            Ok(node_runtime::Error::Balances(
+                balances::Error::InsufficientBalance
+            ))
+        );
+    } else {
+        panic!("expected an error");
+    }
+}
+
 #[async_std::test]
 async fn transfer_subscription() {
     env_logger::try_init().ok();
     let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
     let bob = AccountKeyring::Bob.to_account_id();
-    let bob_addr = bob.clone().into();
+    let bob_addr = bob.clone();
     let cxt = test_context().await;
     let sub = cxt.client().rpc().subscribe_events().await.unwrap();
     let decoder = cxt.client().events_decoder();
@@ -220,10 +705,1149 @@ async fn transfer_subscription() {
 }
 
 #[async_std::test]
-async fn constant_existential_deposit() {
+async fn transfer_subscription_runtime_events() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let sub = cxt.client().rpc().subscribe_events().await.unwrap();
+    let decoder = cxt.client().events_decoder();
+    let mut sub = EventSubscription::<DefaultConfig>::new(sub, &decoder);
+    sub.filter_event::<balances::events::Transfer>();
+    let mut sub = sub.runtime_events::<Event>();
+
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    match sub.next().await.unwrap().unwrap() {
+        Event::Balances(balances::Event::Transfer(from, to, amount)) => {
+            assert_eq!(&from, alice.account_id());
+            assert_eq!(to, bob);
+            assert_eq!(amount, 10_000);
+        }
+        event => panic!("expected a transfer event, got {:?}", event),
+    }
+}
+
+#[async_std::test]
+async fn transfer_subscription_multiple_event_types() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let sub = cxt.client().rpc().subscribe_events().await.unwrap();
+    let decoder = cxt.client().events_decoder();
+    let mut sub = EventSubscription::<DefaultConfig>::new(sub, &decoder)
+        .filter_events::<(balances::events::Transfer, system::events::ExtrinsicSuccess)>(
+        );
+
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    // the transfer extrinsic emits the transfer event followed by its success event, any
+    // other events are dropped
+    let mut transfer = None;
+    loop {
+        match sub.next().await.unwrap().unwrap() {
+            (Some(event), None) => transfer = Some(event),
+            (None, Some(_success)) if transfer.is_some() => break,
+            (None, Some(_success)) => (),
+            _ => panic!("exactly one of the event types should match"),
+        }
+    }
+    assert_eq!(
+        transfer,
+        Some(balances::events::Transfer(
+            alice.account_id().clone(),
+            bob,
+            10_000
+        ))
+    );
+}
+
+#[async_std::test]
+async fn transfer_finalized_subscription() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let rpc = cxt.client().rpc();
+    let sub = rpc.subscribe_finalized_events().await.unwrap();
+    let decoder = cxt.client().events_decoder();
+    let mut sub = EventSubscription::<DefaultConfig>::new(sub, &decoder);
+    sub.filter_event::<balances::events::Transfer>();
+
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let (block, _transfer) = sub.next_with_block().await.unwrap().unwrap();
+    // the block containing the event is already finalized
+    let finalized_head = rpc.finalized_head().await.unwrap();
+    let finalized_number = rpc
+        .header(Some(finalized_head))
+        .await
+        .unwrap()
+        .unwrap()
+        .number;
+    let block_number = rpc.header(Some(block)).await.unwrap().unwrap().number;
+    assert!(block_number <= finalized_number);
+    assert_eq!(
+        rpc.block_hash(Some(block_number.into())).await.unwrap(),
+        Some(block)
+    );
+}
+
+#[async_std::test]
+async fn decode_transfer_into_outer_event() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    let raw = result.find_event_raw("Balances", "Transfer").unwrap();
+
+    let event: Event = cxt.client().events_decoder().decode_event(raw).unwrap();
+    assert_eq!(
+        event,
+        Event::Balances(balances::Event::Transfer(
+            alice.account_id().clone(),
+            bob,
+            10_000
+        ))
+    );
+}
+
+#[async_std::test]
+async fn constant_existential_deposit() {
     let cxt = test_context().await;
     let balances_metadata = cxt.client().metadata().pallet("Balances").unwrap();
     let constant_metadata = balances_metadata.constant("ExistentialDeposit").unwrap();
     let existential_deposit = u128::decode(&mut &constant_metadata.value[..]).unwrap();
     assert_eq!(existential_deposit, 100_000_000_000_000);
 }
+
+#[async_std::test]
+async fn constant_existential_deposit_accessor() {
+    let cxt = test_context().await;
+    let existential_deposit = cxt
+        .api
+        .constants()
+        .balances()
+        .existential_deposit()
+        .unwrap();
+    assert_eq!(existential_deposit, 100_000_000_000_000);
+}
+
+#[async_std::test]
+async fn tx_mortal_transfer() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob_address = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let checkpoint = cxt.client().rpc().block_hash(None).await.unwrap().unwrap();
+    let checkpoint_number = cxt
+        .client()
+        .rpc()
+        .header(Some(checkpoint))
+        .await
+        .unwrap()
+        .unwrap()
+        .number;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob_address, 10_000)
+        .mortal(64, checkpoint)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let included_number = cxt
+        .client()
+        .rpc()
+        .header(Some(result.block))
+        .await
+        .unwrap()
+        .unwrap()
+        .number;
+    assert!(included_number > checkpoint_number);
+    assert!(included_number < checkpoint_number + 64);
+}
+
+#[async_std::test]
+async fn tx_transfer_with_tip() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let untipped = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .create_signed(&alice)
+        .await
+        .unwrap();
+    let tipped = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .tip(1_000)
+        .create_signed(&alice)
+        .await
+        .unwrap();
+
+    let (_, _, untipped_extra) = untipped.signature.unwrap();
+    let (_, _, tipped_extra) = tipped.signature.unwrap();
+    assert_eq!(untipped_extra.6, ChargeTransactionPayment(0));
+    assert_eq!(tipped_extra.6, ChargeTransactionPayment(1_000));
+    assert_ne!(untipped_extra.encode(), tipped_extra.encode());
+}
+
+#[async_std::test]
+async fn dry_run_transfer_error() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let bob_pre = cxt
+        .api
+        .storage()
+        .system()
+        .account(bob.clone(), None)
+        .await
+        .unwrap();
+
+    let res = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), u128::MAX)
+        .dry_run(&alice, None)
+        .await;
+
+    if let Err(Error::Runtime(RuntimeError::Module(error))) = res {
+        let expected = PalletError {
+            pallet: "Balances".into(),
+            error: "InsufficientBalance".into(),
+            description: vec!["Balance too low to send value".to_string()],
+            pallet_index: cxt.client().metadata().pallet("Balances").unwrap().index(),
+            error_index: balances::Error::InsufficientBalance.encode()[0],
+        };
+        assert_eq!(error, expected);
+    } else {
+        panic!("expected an error");
+    }
+
+    let bob_post = cxt.api.storage().system().account(bob, None).await.unwrap();
+    assert_eq!(bob_pre.data.free, bob_post.data.free);
+}
+
+#[async_std::test]
+async fn transfer_fee_estimate() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let info = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .partial_fee_estimate(&alice)
+        .await
+        .unwrap();
+
+    assert_eq!(info.class, DispatchClass::Normal);
+    assert_ne!(info.weight.ref_time, 0);
+    assert_ne!(info.partial_fee, 0);
+}
+
+#[async_std::test]
+async fn tx_submit_without_watching() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let mut hashes = Vec::new();
+    for _ in 0..3 {
+        let hash = cxt
+            .api
+            .tx()
+            .balances()
+            .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+            .sign_and_submit(&alice)
+            .await
+            .unwrap();
+        hashes.push(hash);
+    }
+
+    hashes.dedup();
+    assert_eq!(hashes.len(), 3);
+}
+
+#[async_std::test]
+async fn tx_pipelined_transfers_with_explicit_nonces() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+    let nonce = cxt
+        .client()
+        .rpc()
+        .account_nonce(alice.account_id())
+        .await
+        .unwrap();
+
+    let transfers = (0..3).map(|i| {
+        cxt.api
+            .tx()
+            .balances()
+            .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+            .nonce(nonce + i)
+            .sign_and_submit_then_watch(&alice)
+    });
+    let results = futures::future::join_all(transfers).await;
+
+    for result in results {
+        let success = result
+            .unwrap()
+            .find_event::<system::events::ExtrinsicSuccess>()
+            .unwrap();
+        assert!(success.is_some(), "No ExtrinsicSuccess Event found");
+    }
+}
+
+#[async_std::test]
+async fn tx_transfer_watch_timeout() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+    let nonce = cxt
+        .client()
+        .rpc()
+        .account_nonce(alice.account_id())
+        .await
+        .unwrap();
+
+    // a nonce from the future keeps the extrinsic from being included
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .nonce(nonce + 100)
+        .timeout(std::time::Duration::from_secs(2))
+        .sign_and_submit_then_watch(&alice)
+        .await;
+    assert_matches!(result, Err(Error::Timeout));
+}
+
+#[async_std::test]
+async fn tx_transfer_status_stream() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let statuses = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .sign_and_submit_and_watch_stream(&alice)
+        .await
+        .unwrap()
+        .map(|status| status.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_matches!(statuses.first(), Some(TransactionStatus::Ready));
+    assert!(statuses
+        .iter()
+        .any(|status| matches!(status, TransactionStatus::InBlock(_))));
+    assert_matches!(statuses.last(), Some(TransactionStatus::Finalized(_)));
+}
+
+#[async_std::test]
+async fn tx_signed_offline() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+    let client = cxt.client();
+
+    // everything the offline signer needs, fetched up front
+    let metadata = client.metadata().clone();
+    let runtime_version = client.rpc().runtime_version(None).await.unwrap();
+    let params = OfflineParams {
+        spec_version: runtime_version.spec_version,
+        tx_version: runtime_version.transaction_version,
+        genesis_hash: *client.genesis(),
+        nonce: client
+            .rpc()
+            .account_nonce(alice.account_id())
+            .await
+            .unwrap(),
+        extrinsic_params: Default::default(),
+    };
+
+    let call: balances::calls::Transfer = balances::calls::Transfer {
+        dest: AccountKeyring::Bob.to_account_id().into(),
+        value: 10_000,
+    };
+    let extrinsic = create_signed_offline(&metadata, &call, &alice, params)
+        .await
+        .unwrap();
+    let encoded = extrinsic.encode();
+
+    let result = client
+        .rpc()
+        .submit_and_watch_extrinsic(Encoded(encoded), client.events_decoder())
+        .await
+        .unwrap();
+    let event = result.find_event::<balances::events::Transfer>().unwrap();
+    assert!(event.is_some(), "No Transfer Event found");
+}
+
+#[async_std::test]
+async fn tx_submit_pre_signed_bytes() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let client = cxt.client();
+
+    // the finished bytes, as an external signing tool would hand them over
+    let bytes = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .create_signed(&alice)
+        .await
+        .unwrap()
+        .encode();
+    let result = client
+        .rpc()
+        .submit_and_watch_extrinsic_bytes(bytes.clone(), client.events_decoder())
+        .await
+        .unwrap();
+    assert_eq!(result.extrinsic, BlakeTwo256::hash(&bytes));
+    let event = result.find_event::<balances::events::Transfer>().unwrap();
+    assert_eq!(
+        event,
+        Some(balances::events::Transfer(
+            alice.account_id().clone(),
+            bob.clone(),
+            10_000
+        ))
+    );
+
+    let bytes = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .create_signed(&alice)
+        .await
+        .unwrap()
+        .encode();
+    let hash = client
+        .rpc()
+        .submit_extrinsic_bytes(bytes.clone())
+        .await
+        .unwrap();
+    assert_eq!(hash, BlakeTwo256::hash(&bytes));
+}
+
+#[async_std::test]
+async fn tx_transfer_submit_and_find_event() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let transfer = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .submit_and_find_event::<balances::events::Transfer>(&alice)
+        .await
+        .unwrap();
+    assert_eq!(
+        transfer,
+        balances::events::Transfer(alice.account_id().clone(), bob.clone(), 10_000)
+    );
+
+    // a transfer does not reserve any balance
+    let reserved = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .submit_and_find_event::<balances::events::Reserved>(&alice)
+        .await;
+    assert_matches!(reserved, Err(Error::Other(message)) if message.contains("Reserved"));
+}
+
+#[async_std::test]
+async fn tx_transfers_through_transaction_manager() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let client = cxt.client();
+    let nonce = client
+        .rpc()
+        .account_nonce(alice.account_id())
+        .await
+        .unwrap();
+
+    let manager = TransactionManager::new(client.clone(), alice);
+    let transfers = (0..10).map(|value| {
+        let call: balances::calls::Transfer = balances::calls::Transfer {
+            dest: bob.clone().into(),
+            value: 10_000 + value,
+        };
+        manager.submit(call)
+    });
+    let results = futures::future::join_all(transfers).await;
+    assert_eq!(manager.next_nonce().await, Some(nonce + 10));
+
+    let mut nonces = Vec::new();
+    for result in results {
+        let result = result.unwrap();
+        assert!(result
+            .find_event::<balances::events::Transfer>()
+            .unwrap()
+            .is_some());
+        let extrinsics = client
+            .block_extrinsics::<Call>(result.block)
+            .await
+            .unwrap()
+            .unwrap();
+        let (_, _, extra) = extrinsics[result.index as usize]
+            .signature
+            .as_ref()
+            .unwrap();
+        nonces.push(DefaultExtra::<DefaultConfig>::nonce_of(extra));
+    }
+    nonces.sort_unstable();
+    assert_eq!(nonces, (nonce..nonce + 10).collect::<Vec<_>>());
+}
+
+#[async_std::test]
+async fn transaction_manager_resyncs_after_rejected_submission() {
+    let hans = PairSigner::<DefaultConfig, _>::new(Pair::generate().0);
+    let cxt = test_context().await;
+
+    let manager = TransactionManager::new(cxt.client().clone(), hans);
+    // the pool rejects the transfer, since hans can't pay the fees
+    let call: balances::calls::Transfer = balances::calls::Transfer {
+        dest: AccountKeyring::Alice.to_account_id().into(),
+        value: 10_000,
+    };
+    let result = manager.submit(call).await;
+    assert_matches!(result, Err(Error::Rpc(_)));
+    assert_eq!(manager.next_nonce().await, None);
+}
+
+#[async_std::test]
+async fn tx_transfer_watch_finalized() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .sign_and_submit_then_watch_finalized(&alice)
+        .await
+        .unwrap();
+
+    let rpc = cxt.client().rpc();
+    let finalized_head = rpc.finalized_head().await.unwrap();
+    let finalized_number = rpc
+        .header(Some(finalized_head))
+        .await
+        .unwrap()
+        .unwrap()
+        .number;
+    let block_number = rpc
+        .header(Some(result.block))
+        .await
+        .unwrap()
+        .unwrap()
+        .number;
+    assert!(block_number <= finalized_number);
+
+    let canonical_hash = rpc.block_hash(Some(block_number.into())).await.unwrap();
+    assert_eq!(canonical_hash, Some(result.block));
+}
+
+#[async_std::test]
+async fn construct_transfer_call_without_submitting() {
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let call = cxt.api.tx().balances().transfer_call(bob.clone(), 10_000);
+    assert_eq!(
+        call,
+        balances::Call::transfer {
+            dest: bob.clone().into(),
+            value: 10_000,
+        }
+    );
+
+    // the pallet index and the call index precede the arguments
+    let encoded = hex::encode(Call::Balances(call).encode());
+    let transfer: balances::calls::Transfer = balances::calls::Transfer {
+        dest: bob.into(),
+        value: 10_000,
+    };
+    assert_eq!(encoded, format!("0600{}", hex::encode(transfer.encode())));
+}
+
+#[async_std::test]
+async fn tx_transfer_in_block_and_finalized_hashes() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let finalized = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch_finalized(&alice)
+        .await
+        .unwrap();
+    assert_eq!(finalized.finalized_hash(), Some(finalized.block));
+    // there are no reorgs on the dev chain, so the extrinsic stays in its first block
+    assert_eq!(finalized.block_hash(), finalized.block);
+
+    let client: Client<DefaultConfig> = ClientBuilder::new()
+        .set_url(cxt.node_proc.ws_url())
+        .accept_weak_inclusion()
+        .build()
+        .await
+        .unwrap();
+    let api = client.to_runtime_api::<node_runtime::RuntimeApi<DefaultConfig>>();
+    let in_block = api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    assert_eq!(in_block.finalized_hash(), None);
+    assert_eq!(in_block.block_hash(), in_block.block);
+}
+
+#[async_std::test]
+async fn storage_account_subscribe() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let api = &cxt.api;
+
+    let mut subscription = api
+        .storage()
+        .system()
+        .account_subscribe(bob.clone())
+        .await
+        .unwrap();
+    // the current value is sent when subscribing
+    let (_, bob_pre) = subscription.next().await.unwrap().unwrap();
+    let bob_pre = bob_pre.unwrap();
+
+    api.tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let (_, bob_post) = subscription.next().await.unwrap().unwrap();
+    assert_eq!(bob_pre.data.free + 10_000, bob_post.unwrap().data.free);
+}
+
+#[async_std::test]
+async fn storage_total_issuance_at_number() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+    let api = &cxt.api;
+
+    // make sure a block other than genesis has been produced
+    api.tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let block_1_hash = cxt
+        .client()
+        .rpc()
+        .block_hash(Some(1u32.into()))
+        .await
+        .unwrap();
+    let at_block_1 = api
+        .storage()
+        .balances()
+        .total_issuance_at_number(1)
+        .await
+        .unwrap();
+    assert_eq!(
+        at_block_1,
+        api.storage()
+            .balances()
+            .total_issuance(block_1_hash)
+            .await
+            .unwrap()
+    );
+
+    let head_hash = cxt.client().rpc().block_hash(None).await.unwrap().unwrap();
+    let head_number = cxt
+        .client()
+        .rpc()
+        .header(Some(head_hash))
+        .await
+        .unwrap()
+        .unwrap()
+        .number;
+    let at_head = api
+        .storage()
+        .balances()
+        .total_issuance_at_number(head_number)
+        .await
+        .unwrap();
+    assert_eq!(
+        at_head,
+        api.storage()
+            .balances()
+            .total_issuance(Some(head_hash))
+            .await
+            .unwrap()
+    );
+
+    let unknown = api
+        .storage()
+        .balances()
+        .total_issuance_at_number(u32::MAX)
+        .await;
+    assert!(unknown.is_err());
+}
+
+#[async_std::test]
+async fn events_at_transfer_block() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let events = cxt.client().events_at(result.block).await.unwrap();
+    let transfers = events
+        .iter()
+        .filter_map(|record| {
+            record
+                .as_event::<balances::events::Transfer>()
+                .unwrap()
+                .map(|event| (record.extrinsic_index(), event))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(transfers.len(), 1);
+    let (extrinsic_index, transfer) = transfers.into_iter().next().unwrap();
+    // the transfer is applied after the timestamp inherent
+    assert!(matches!(extrinsic_index, Some(index) if index > 0));
+    assert_eq!(
+        transfer,
+        balances::events::Transfer(alice.account_id().clone(), bob, 10_000)
+    );
+}
+
+#[async_std::test]
+async fn transfer_block_events_grouped_by_phase() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let groups = result.block_events(cxt.client()).await.unwrap();
+    let (_, receipt) = groups
+        .iter()
+        .find(|(phase, _)| *phase == Phase::ApplyExtrinsic(result.index))
+        .unwrap();
+    let names = receipt
+        .iter()
+        .filter_map(|event| {
+            match event {
+                Raw::Event(event) => {
+                    Some((event.pallet.as_str(), event.variant.as_str()))
+                }
+                Raw::Error(_) => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    assert!(names.contains(&("Balances", "Transfer")));
+    assert!(names.contains(&("System", "ExtrinsicSuccess")));
+    assert_eq!(names.len(), result.events.len());
+    // the timestamp inherent is applied in its own phase before the transfer
+    assert!(groups.len() > 1);
+}
+
+#[async_std::test]
+async fn block_extrinsics_contain_transfer_call() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let extrinsics = cxt
+        .client()
+        .block_extrinsics::<Call>(result.block)
+        .await
+        .unwrap()
+        .unwrap();
+    let transfers = extrinsics
+        .into_iter()
+        .filter(|extrinsic| {
+            matches!(
+                &extrinsic.function,
+                Call::Balances(balances::Call::transfer { value: 10_000, .. })
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(transfers.len(), 1);
+    let (address, _, _) = transfers[0].signature.as_ref().unwrap();
+    assert_eq!(address, &MultiAddress::Id(alice.account_id().clone()));
+}
+
+#[async_std::test]
+async fn block_extrinsics_contain_signed_extensions() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let nonce = cxt
+        .client()
+        .rpc()
+        .account_nonce(alice.account_id())
+        .await
+        .unwrap();
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .tip(1_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let extrinsics = cxt
+        .client()
+        .block_extrinsics::<Call>(result.block)
+        .await
+        .unwrap()
+        .unwrap();
+    let (address, _, extra) = extrinsics
+        .iter()
+        .find(|extrinsic| matches!(&extrinsic.function, Call::Balances(_)))
+        .and_then(|extrinsic| extrinsic.signature.as_ref())
+        .unwrap();
+    assert_eq!(address, &MultiAddress::Id(alice.account_id().clone()));
+    let (_, _, _, _, check_nonce, _, charge_transaction_payment) = extra;
+    assert_eq!(check_nonce.0, nonce);
+    assert_eq!(charge_transaction_payment.0, 1_000);
+}
+
+#[async_std::test]
+async fn submittable_extrinsic_inspect_sign_and_submit() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let extrinsic = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .tip(100);
+    assert_eq!(extrinsic.call().value, 10_000);
+    assert_eq!(extrinsic.call().dest, MultiAddress::Id(bob));
+
+    let pallet = cxt.client().metadata().pallet("Balances").unwrap();
+    let encoded_call = extrinsic.encoded_call().unwrap();
+    assert_eq!(encoded_call.0[0], pallet.index());
+    assert_eq!(encoded_call.0[1], pallet.call("transfer").unwrap().index());
+    assert_eq!(&encoded_call.0[2..], &extrinsic.call().encode()[..]);
+
+    let fee = extrinsic.partial_fee_estimate(&alice).await.unwrap();
+    assert_ne!(fee.partial_fee, 0);
+    assert_eq!(extrinsic.clone().call(), extrinsic.call());
+
+    let signed = extrinsic.create_signed(&alice).await.unwrap();
+    let result = cxt
+        .client()
+        .rpc()
+        .submit_and_watch_extrinsic(signed, cxt.client().events_decoder())
+        .await
+        .unwrap();
+    assert!(result
+        .find_event::<balances::events::Transfer>()
+        .unwrap()
+        .is_some());
+}
+
+#[async_std::test]
+async fn signed_transfer_hash_matches_node() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+    let rpc = cxt.client().rpc();
+    let nonce = rpc.account_nonce(alice.account_id()).await.unwrap();
+
+    let extrinsic = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .nonce(nonce)
+        .create_signed(&alice)
+        .await
+        .unwrap();
+    let hash = extrinsic.hash();
+    assert_eq!(hash, BlakeTwo256::hash(&extrinsic.encoded().0));
+    assert_eq!(
+        rpc.submit_extrinsic(extrinsic.encoded()).await.unwrap(),
+        hash
+    );
+
+    let extrinsic = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .nonce(nonce + 1)
+        .create_signed(&alice)
+        .await
+        .unwrap();
+    let hash = extrinsic.hash();
+    let mut statuses = rpc
+        .watch_extrinsic_status(extrinsic.encoded())
+        .await
+        .unwrap();
+    let block_hash = loop {
+        match statuses.next().await.unwrap().unwrap() {
+            TransactionStatus::InBlock(block_hash) => break block_hash,
+            status => assert!(!status.is_final(), "unexpected status {:?}", status),
+        }
+    };
+    let block = rpc.block(Some(block_hash)).await.unwrap().unwrap();
+    assert!(block
+        .block
+        .extrinsics
+        .iter()
+        .any(|extrinsic| BlakeTwo256::hash_of(extrinsic) == hash));
+}
+
+#[async_std::test]
+async fn submitted_transfer_is_pending() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let hash = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit(&alice)
+        .await
+        .unwrap();
+
+    // the transfer stays in the pool until the next block is authored
+    let pending = cxt.client().rpc().pending_extrinsics().await.unwrap();
+    assert!(pending
+        .iter()
+        .any(|bytes| BlakeTwo256::hash(&bytes[..]) == hash));
+
+    let pending = cxt.client().pending_extrinsics::<Call>().await.unwrap();
+    assert!(pending.iter().any(|extrinsic| {
+        matches!(
+            &extrinsic.function,
+            Call::Balances(balances::Call::transfer { dest, value: 10_000 })
+                if dest == &MultiAddress::Id(bob.clone())
+        )
+    }));
+}
+
+#[async_std::test]
+async fn transfer_dispatch_info() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let dispatch_info = result
+        .dispatch_info(cxt.client().metadata())
+        .unwrap()
+        .unwrap();
+    assert!(dispatch_info.weight.ref_time > 0);
+    assert_eq!(dispatch_info.class, DispatchClass::Normal);
+    assert_eq!(dispatch_info.pays_fee, Pays::Yes);
+}
+
+#[async_std::test]
+async fn storage_query_range() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+    let bob_key = cxt.api.storage().system().account_key(bob.clone());
+    let total_issuance_key =
+        StorageKeyPrefix::new::<balances::storage::TotalIssuance>().to_storage_key();
+
+    // each transfer is included in a different block
+    for _ in 0..2 {
+        cxt.api
+            .tx()
+            .balances()
+            .transfer(bob.clone(), 10_000)
+            .sign_and_submit_then_watch(&alice)
+            .await
+            .unwrap();
+    }
+
+    let rpc = cxt.client().rpc();
+    let latest = rpc.header(None).await.unwrap().unwrap().number;
+    let change_sets = cxt
+        .client()
+        .storage()
+        .query_storage_range(vec![bob_key.clone(), total_issuance_key], 0, latest)
+        .await
+        .unwrap();
+    assert_eq!(change_sets[0].block, *cxt.client().genesis());
+
+    let mut numbers = Vec::new();
+    for change_set in &change_sets {
+        let header = rpc.header(Some(change_set.block)).await.unwrap().unwrap();
+        numbers.push(header.number);
+    }
+    let mut sorted = numbers.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(numbers, sorted);
+
+    // the genesis value and the value after each transfer
+    let bob_changes = change_sets
+        .iter()
+        .filter(|change_set| change_set.changes.iter().any(|(key, _)| key == &bob_key))
+        .count();
+    assert!(bob_changes >= 3);
+}
+
+#[async_std::test]
+async fn storage_total_issuance_raw() {
+    let cxt = test_context().await;
+    let storage = cxt.client().storage();
+
+    let key =
+        StorageKeyPrefix::new::<balances::storage::TotalIssuance>().to_storage_key();
+    let data = storage.fetch_raw(key, None).await.unwrap().unwrap();
+    let total_issuance = u128::decode(&mut &data.0[..]).unwrap();
+    assert_eq!(
+        total_issuance,
+        cxt.api
+            .storage()
+            .balances()
+            .total_issuance(None)
+            .await
+            .unwrap()
+    );
+
+    let alice_key = cxt
+        .api
+        .storage()
+        .system()
+        .account_key(AccountKeyring::Alice.to_account_id());
+    let prefix = StorageKeyPrefix::new::<system::storage::Account>().to_storage_key();
+    let keys = storage
+        .fetch_keys_raw(prefix, 100, None, None)
+        .await
+        .unwrap();
+    assert!(keys.contains(&alice_key));
+}
+
+#[async_std::test]
+async fn transfers_in_same_block_have_different_phases() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Bob.pair());
+    let cxt = test_context().await;
+    let api = &cxt.api;
+
+    let alice_to_charlie = api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Charlie.to_account_id(), 10_000);
+    let bob_to_charlie = api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Charlie.to_account_id(), 10_000);
+    let (alice_result, bob_result) = futures::future::join(
+        alice_to_charlie.sign_and_submit_then_watch(&alice),
+        bob_to_charlie.sign_and_submit_then_watch(&bob),
+    )
+    .await;
+    let (alice_result, bob_result) = (alice_result.unwrap(), bob_result.unwrap());
+    assert_eq!(alice_result.block, bob_result.block);
+
+    let alice_transfer = alice_result.find_event_raw("Balances", "Transfer").unwrap();
+    let bob_transfer = bob_result.find_event_raw("Balances", "Transfer").unwrap();
+    assert!(alice_transfer.extrinsic_index().is_some());
+    assert!(bob_transfer.extrinsic_index().is_some());
+    assert_ne!(alice_transfer.phase, bob_transfer.phase);
+}
@@ -0,0 +1,88 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+    node_runtime::{
+        democracy,
+        Call,
+        DefaultConfig,
+    },
+    test_context,
+};
+use codec::Encode;
+use sp_keyring::AccountKeyring;
+use subxt::{
+    extrinsic::{
+        PairSigner,
+        Signer,
+    },
+    preimage_hash,
+};
+
+/// Above the minimum deposit of a proposal on the dev chain.
+const PROPOSAL_DEPOSIT: u128 = 100_000_000_000_000_000;
+
+#[async_std::test]
+async fn tx_note_preimage_and_propose() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let proposal = Call::Balances(
+        cxt.api
+            .tx()
+            .balances()
+            .transfer_call(AccountKeyring::Bob.to_account_id(), 10_000),
+    );
+    let hash = preimage_hash::<DefaultConfig, _>(&proposal);
+
+    let result = cxt
+        .api
+        .tx()
+        .democracy()
+        .note_preimage(proposal.encode())
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    let noted = result
+        .find_event::<democracy::events::PreimageNoted>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(noted.0, hash);
+    assert_eq!(&noted.1, alice.account_id());
+
+    let preimage = cxt
+        .api
+        .storage()
+        .democracy()
+        .preimages(hash, None)
+        .await
+        .unwrap();
+    assert!(preimage.is_some());
+
+    let result = cxt
+        .api
+        .tx()
+        .democracy()
+        .propose(hash, PROPOSAL_DEPOSIT)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    let proposed = result
+        .find_event::<democracy::events::Proposed>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(proposed.1, PROPOSAL_DEPOSIT);
+}
@@ -18,6 +18,9 @@
 
 mod balances;
 mod contracts;
+mod democracy;
+mod proxy;
 mod staking;
 mod sudo;
 mod system;
+mod utility;
@@ -0,0 +1,95 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+    node_runtime::{
+        balances,
+        proxy,
+        runtime_types::node_runtime::ProxyType,
+        Call,
+        DefaultConfig,
+    },
+    test_context,
+};
+use sp_keyring::AccountKeyring;
+use subxt::extrinsic::PairSigner;
+
+#[async_std::test]
+async fn tx_transfer_through_proxy() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Bob.pair());
+    let charlie = AccountKeyring::Charlie.to_account_id();
+    let cxt = test_context().await;
+
+    // bob allows alice to dispatch any call on his behalf
+    let added = cxt
+        .api
+        .tx()
+        .proxy()
+        .add_proxy(AccountKeyring::Alice.to_account_id(), ProxyType::Any, 0)
+        .sign_and_submit_then_watch(&bob)
+        .await
+        .unwrap();
+    assert!(matches!(
+        added.find_event::<proxy::events::ProxyAdded>(),
+        Ok(Some(_))
+    ));
+
+    let call = Call::Balances(balances::Call::transfer {
+        dest: charlie.clone().into(),
+        value: 10_000,
+    });
+    let result = cxt
+        .api
+        .tx()
+        .proxy()
+        .proxy(AccountKeyring::Bob.to_account_id(), None, call)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    assert_eq!(result.proxy_executed().unwrap(), Some(Ok(())));
+    let transfer = result.find_event::<balances::events::Transfer>().unwrap();
+    assert_eq!(
+        transfer,
+        Some(balances::events::Transfer(
+            AccountKeyring::Bob.to_account_id(),
+            charlie,
+            10_000
+        ))
+    );
+}
+
+#[async_std::test]
+async fn tx_proxy_without_permission_fails() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let call = Call::Balances(balances::Call::transfer {
+        dest: AccountKeyring::Charlie.to_account_id().into(),
+        value: 10_000,
+    });
+    let result = cxt
+        .api
+        .tx()
+        .proxy()
+        .proxy(AccountKeyring::Dave.to_account_id(), None, call)
+        .sign_and_submit_then_watch(&alice)
+        .await;
+
+    // alice is not a proxy of dave, so the extrinsic itself fails
+    assert!(result.is_err());
+}
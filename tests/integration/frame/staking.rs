@@ -15,6 +15,8 @@
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
+    encode_event_records,
+    fixture_metadata,
     node_runtime::{
         runtime_types::pallet_staking::{
             RewardDestination,
@@ -23,6 +25,7 @@ use crate::{
         staking,
         system,
         DefaultConfig,
+        Event,
     },
     test_context,
 };
@@ -37,8 +40,14 @@ use subxt::{
         PairSigner,
         Signer,
     },
+    storage::StorageKeyPrefix,
     Error,
+    EventsDecoder,
+    Phase,
     RuntimeError,
+    StorageEntryKey,
+    StorageHasher,
+    StorageMapKey,
 };
 
 /// Helper function to generate a crypto pair from seed
@@ -193,7 +202,7 @@ async fn tx_bond() -> Result<(), Error> {
         .tx()
         .staking()
         .bond(
-            AccountKeyring::Bob.to_account_id().into(),
+            AccountKeyring::Bob.to_account_id(),
             100_000_000_000_000,
             RewardDestination::Stash,
         )
@@ -207,7 +216,7 @@ async fn tx_bond() -> Result<(), Error> {
         .tx()
         .staking()
         .bond(
-            AccountKeyring::Bob.to_account_id().into(),
+            AccountKeyring::Bob.to_account_id(),
             100_000_000_000_000,
             RewardDestination::Stash,
         )
@@ -222,6 +231,79 @@ async fn tx_bond() -> Result<(), Error> {
     Ok(())
 }
 
+#[async_std::test]
+async fn tx_bond_nominate_and_set_payee() -> Result<(), Error> {
+    let charlie = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Charlie.pair());
+    let alice_stash = PairSigner::<DefaultConfig, _>::new(get_from_seed("Alice//stash"));
+    let cxt = test_context().await;
+
+    cxt.api
+        .tx()
+        .staking()
+        .bond(
+            charlie.account_id().clone(),
+            100_000_000_000_000,
+            RewardDestination::Stash,
+        )
+        .sign_and_submit_then_watch(&charlie)
+        .await?;
+
+    let targets = vec![alice_stash.account_id().clone()];
+    cxt.api
+        .tx()
+        .staking()
+        .nominate(targets.iter().cloned().map(Into::into).collect())
+        .sign_and_submit_then_watch(&charlie)
+        .await?;
+
+    let nominations = cxt
+        .api
+        .storage()
+        .staking()
+        .nominators(charlie.account_id().clone(), None)
+        .await?
+        .expect("Charlie should be nominating");
+    assert_eq!(nominations.targets, targets);
+
+    cxt.api
+        .tx()
+        .staking()
+        .set_payee(RewardDestination::Controller)
+        .sign_and_submit_then_watch(&charlie)
+        .await?;
+
+    let payee = cxt
+        .api
+        .storage()
+        .staking()
+        .payee(charlie.account_id().clone(), None)
+        .await?;
+    assert_eq!(payee, RewardDestination::Controller);
+    Ok(())
+}
+
+#[async_std::test]
+async fn tx_payout_stakers_for_unfinished_era() -> Result<(), Error> {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let alice_stash = PairSigner::<DefaultConfig, _>::new(get_from_seed("Alice//stash"));
+    let cxt = test_context().await;
+
+    // the first era has not ended yet, so there are no rewards to pay out
+    let payout = cxt
+        .api
+        .tx()
+        .staking()
+        .payout_stakers(alice_stash.account_id().clone(), 0)
+        .sign_and_submit_then_watch(&alice)
+        .await;
+
+    assert_matches!(payout, Err(Error::Runtime(RuntimeError::Module(module_err))) => {
+        assert_eq!(module_err.pallet, "Staking");
+        assert_eq!(module_err.error, "InvalidEraToReward");
+    });
+    Ok(())
+}
+
 #[async_std::test]
 async fn storage_history_depth() -> Result<(), Error> {
     let cxt = test_context().await;
@@ -256,3 +338,66 @@ async fn storage_era_reward_points() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[async_std::test]
+async fn storage_eras_stakers_iter_prefix() -> Result<(), Error> {
+    let cxt = test_context().await;
+    let staking = cxt.api.storage().staking();
+    let era = 0;
+
+    let mut era_keys = Vec::new();
+    let mut iter = staking.eras_stakers_iter_prefix(era, None).await?;
+    while let Some((key, _exposure)) = iter.next().await? {
+        era_keys.push(key);
+    }
+    assert!(!era_keys.is_empty());
+
+    let prefix =
+        StorageEntryKey::Map(vec![StorageMapKey::new(&era, StorageHasher::Twox64Concat)])
+            .final_key(StorageKeyPrefix::new::<staking::storage::ErasStakers>());
+    let mut all_era_keys = Vec::new();
+    let mut iter = staking.eras_stakers_iter(None).await?;
+    while let Some((key, _exposure)) = iter.next().await? {
+        if key.0.starts_with(&prefix.0) {
+            all_era_keys.push(key);
+        }
+    }
+    era_keys.sort();
+    all_era_keys.sort();
+    assert_eq!(era_keys, all_era_keys);
+    Ok(())
+}
+
+#[test]
+fn decode_payout_events() {
+    let decoder = EventsDecoder::<DefaultConfig>::new(fixture_metadata());
+
+    let stash = AccountKeyring::Alice.to_account_id();
+    let events = vec![
+        Event::Staking(staking::Event::PayoutStarted(1, stash.clone())),
+        Event::Staking(staking::Event::Rewarded(stash.clone(), 10_000)),
+    ];
+
+    // the event records of a payout extrinsic
+    let input = encode_event_records(
+        events
+            .into_iter()
+            .map(|event| (Phase::ApplyExtrinsic(1), event)),
+    );
+
+    let records = decoder
+        .decode_events_iter(&input)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        records[0]
+            .as_event::<staking::events::PayoutStarted>()
+            .unwrap(),
+        Some(staking::events::PayoutStarted(1, stash.clone()))
+    );
+    assert_eq!(
+        records[1].as_event::<staking::events::Rewarded>().unwrap(),
+        Some(staking::events::Rewarded(stash, 10_000))
+    );
+}
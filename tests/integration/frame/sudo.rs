@@ -16,6 +16,7 @@
 
 use crate::{
     node_runtime::{
+        balances,
         runtime_types,
         sudo,
         DefaultConfig,
@@ -75,3 +76,34 @@ async fn test_sudo_unchecked_weight() {
     let sudid = res.find_event::<sudo::events::Sudid>();
     assert_matches!(sudid, Ok(Some(_)))
 }
+
+#[async_std::test]
+async fn test_sudo_force_transfer() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let charlie = AccountKeyring::Charlie.to_account_id();
+    let cxt = test_context().await;
+
+    let call = Call::Balances(BalancesCall::force_transfer {
+        source: bob.clone().into(),
+        dest: charlie.clone().into(),
+        value: 10_000,
+    });
+
+    let res = cxt
+        .api
+        .tx()
+        .sudo()
+        .sudo(call)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let sudid = res.find_event::<sudo::events::Sudid>();
+    assert_matches!(sudid, Ok(Some(sudo::events::Sudid(Ok(())))));
+    let transfer = res.find_event::<balances::events::Transfer>().unwrap();
+    assert_eq!(
+        transfer,
+        Some(balances::events::Transfer(bob, charlie, 10_000))
+    );
+}
@@ -15,17 +15,43 @@
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
+    fixture_metadata,
     node_runtime::{
+        balances,
         system,
         DefaultConfig,
     },
     test_context,
 };
 use assert_matches::assert_matches;
+use codec::Encode;
+use frame_metadata::{
+    v14::{
+        ExtrinsicMetadata,
+        PalletEventMetadata,
+        PalletMetadata,
+        RuntimeMetadataV14,
+        StorageEntryType,
+    },
+    RuntimeMetadata,
+    RuntimeMetadataPrefixed,
+    META_RESERVED,
+};
+use scale_info::meta_type;
+use sp_core::crypto::AccountId32;
 use sp_keyring::AccountKeyring;
-use subxt::extrinsic::{
-    PairSigner,
-    Signer,
+use subxt::{
+    extrinsic::{
+        PairSigner,
+        Signer,
+    },
+    storage::StorageKeyPrefix,
+    DispatchClass,
+    DispatchInfo,
+    Metadata,
+    Pays,
+    StorageHasher,
+    Weight,
 };
 
 #[async_std::test]
@@ -42,6 +68,234 @@ async fn storage_account() {
     assert_matches!(account_info, Ok(_))
 }
 
+#[async_std::test]
+async fn account_balance_matches_account_info() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(bob.clone(), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    for account in [alice.account_id().clone(), bob] {
+        let info = cxt
+            .api
+            .storage()
+            .system()
+            .account(account.clone(), None)
+            .await
+            .unwrap();
+        let balance = cxt.client().account_balance(&account, None).await.unwrap();
+        assert_eq!(balance.free, info.data.free);
+        assert_eq!(balance.reserved, info.data.reserved);
+        assert_eq!(
+            balance.frozen,
+            info.data.misc_frozen.max(info.data.fee_frozen)
+        );
+        // the node stores no flags
+        assert_eq!(balance.flags, None);
+    }
+
+    // accounts which were never endowed have no balance
+    let unknown = AccountId32::new([7; 32]);
+    let balance = cxt.client().account_balance(&unknown, None).await.unwrap();
+    assert_eq!(balance, Default::default());
+}
+
+#[async_std::test]
+async fn storage_account_iter_decode_keys() {
+    let cxt = test_context().await;
+    let storage = cxt.client().storage();
+
+    let mut account_ids = Vec::new();
+    let mut iter = cxt.api.storage().system().account_iter(None).await.unwrap();
+    while let Some((key, _account)) = iter.next().await.unwrap() {
+        let account_id = storage
+            .decode_map_key::<system::storage::Account, AccountId32>(&key)
+            .unwrap();
+        account_ids.push(account_id);
+    }
+
+    for account in [
+        AccountKeyring::Alice,
+        AccountKeyring::Bob,
+        AccountKeyring::Charlie,
+        AccountKeyring::Dave,
+        AccountKeyring::Eve,
+        AccountKeyring::Ferdie,
+    ] {
+        assert!(account_ids.contains(&account.to_account_id()));
+    }
+}
+
+#[async_std::test]
+async fn storage_block_weight_after_transfer() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    assert!(result
+        .find_event::<balances::events::Transfer>()
+        .unwrap()
+        .is_some());
+
+    // the node weighs with a plain `u64`, so the classes decode as such
+    let block_weight = cxt
+        .api
+        .storage()
+        .system()
+        .block_weight(Some(result.block))
+        .await
+        .unwrap();
+    assert!(block_weight.normal > 0);
+}
+
+#[async_std::test]
+async fn storage_events_size_after_transfer() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(bob, 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let storage = cxt.client().storage();
+    let key = StorageKeyPrefix::new::<system::storage::Events>().to_storage_key();
+    let size = storage
+        .size(key.clone(), Some(result.block))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(size > 0);
+
+    let events = storage
+        .fetch_raw(key, Some(result.block))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(size, events.0.len() as u64);
+}
+
+#[test]
+fn metadata_storage_account_prefix_and_hashers() {
+    let metadata = fixture_metadata();
+
+    let pallet = metadata.pallet("System").unwrap();
+    assert_eq!(pallet.storage_prefix(), Some("System"));
+    let account = pallet.storage("Account").unwrap();
+    assert_matches!(
+        &account.ty,
+        StorageEntryType::Map { hashers, .. } if hashers == &[StorageHasher::Blake2_128Concat]
+    );
+
+    // the prefix of the keys of `System::Account` is the hash of the pallet and entry names
+    let prefix =
+        StorageKeyPrefix::from_names(pallet.storage_prefix().unwrap(), &account.name);
+    assert_eq!(
+        hex::encode(prefix.to_storage_key().0),
+        "26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9"
+    );
+}
+
+#[async_std::test]
+async fn storage_account_key() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+
+    let cxt = test_context().await;
+    let key = cxt
+        .api
+        .storage()
+        .system()
+        .account_key(alice.account_id().clone().into());
+    assert_eq!(
+        hex::encode(key.0),
+        "26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9\
+         de1e86a9a8c739864cf3cc5ec2bea59fd43593c715fdd31c61141abd04a99fd6\
+         822c8558854ccde39a5684e7a56da27d"
+    );
+}
+
+#[async_std::test]
+async fn account_nonce_includes_pending_transactions() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+    let rpc = cxt.client().rpc();
+
+    let nonce = rpc.account_nonce(alice.account_id()).await.unwrap();
+    cxt.api
+        .tx()
+        .system()
+        .remark(b"pending".to_vec())
+        .sign_and_submit(&alice)
+        .await
+        .unwrap();
+
+    // the pending remark is accounted for by the rpc nonce
+    let pending_nonce = rpc.account_nonce(alice.account_id()).await.unwrap();
+    assert_eq!(pending_nonce, nonce + 1);
+
+    // but only by the storage nonce once it is included in a block, which it is by the
+    // time a later extrinsic of alice is
+    let result = cxt
+        .api
+        .tx()
+        .system()
+        .remark(b"included".to_vec())
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    let storage_nonce = cxt
+        .api
+        .storage()
+        .system()
+        .account(alice.account_id().clone(), Some(result.block))
+        .await
+        .unwrap()
+        .nonce;
+    assert_eq!(storage_nonce, nonce + 2);
+}
+
+#[async_std::test]
+async fn storage_absent_account_is_default() {
+    let cxt = test_context().await;
+    let account = AccountId32::from([7u8; 32]);
+
+    // the account has never been written to storage
+    let key = cxt.api.storage().system().account_key(account.clone());
+    let raw = cxt.client().storage().fetch_raw(key, None).await.unwrap();
+    assert_eq!(raw, None);
+
+    let account_info = cxt
+        .api
+        .storage()
+        .system()
+        .account(account, None)
+        .await
+        .unwrap();
+    assert_eq!(account_info.nonce, 0);
+    assert_eq!(account_info.providers, 0);
+    assert_eq!(account_info.data.free, 0);
+}
+
 #[async_std::test]
 async fn tx_remark_with_event() {
     let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
@@ -59,3 +313,92 @@ async fn tx_remark_with_event() {
     let remarked = result.find_event::<system::events::Remarked>();
     assert_matches!(remarked, Ok(Some(_)));
 }
+
+#[test]
+fn decode_dispatch_info_with_weight_v2() {
+    // the types of a runtime with the two dimensional weights of Weight v2
+    mod runtime {
+        use codec::Encode;
+        use scale_info::TypeInfo;
+
+        #[derive(Encode, TypeInfo)]
+        pub struct Weight {
+            #[codec(compact)]
+            pub ref_time: u64,
+            #[codec(compact)]
+            pub proof_size: u64,
+        }
+
+        #[allow(unused)]
+        #[derive(Encode, TypeInfo)]
+        pub enum DispatchClass {
+            Normal,
+            Operational,
+            Mandatory,
+        }
+
+        #[allow(unused)]
+        #[derive(Encode, TypeInfo)]
+        pub enum Pays {
+            Yes,
+            No,
+        }
+
+        #[derive(Encode, TypeInfo)]
+        pub struct DispatchInfo {
+            pub weight: Weight,
+            pub class: DispatchClass,
+            pub pays_fee: Pays,
+        }
+
+        #[derive(Encode, TypeInfo)]
+        pub enum Event {
+            ExtrinsicSuccess(DispatchInfo),
+        }
+    }
+
+    let pallets = vec![PalletMetadata {
+        name: "System",
+        storage: None,
+        calls: None,
+        event: Some(PalletEventMetadata {
+            ty: meta_type::<runtime::Event>(),
+        }),
+        constants: vec![],
+        error: None,
+        index: 0,
+    }];
+    let extrinsic = ExtrinsicMetadata {
+        ty: meta_type::<()>(),
+        version: 4,
+        signed_extensions: vec![],
+    };
+    let runtime_metadata = RuntimeMetadataV14::new(pallets, extrinsic, meta_type::<()>());
+    let metadata: Metadata =
+        RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(runtime_metadata))
+            .try_into()
+            .unwrap();
+
+    let data = runtime::DispatchInfo {
+        weight: runtime::Weight {
+            ref_time: 1_000_000,
+            proof_size: 4_096,
+        },
+        class: runtime::DispatchClass::Operational,
+        pays_fee: runtime::Pays::No,
+    }
+    .encode();
+    let dispatch_info =
+        DispatchInfo::decode_with_metadata(&mut &data[..], &metadata).unwrap();
+    assert_eq!(
+        dispatch_info,
+        DispatchInfo {
+            weight: Weight {
+                ref_time: 1_000_000,
+                proof_size: 4_096,
+            },
+            class: DispatchClass::Operational,
+            pays_fee: Pays::No,
+        }
+    );
+}
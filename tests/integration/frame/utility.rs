@@ -0,0 +1,135 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+    node_runtime::{
+        balances,
+        utility,
+        Call,
+        DefaultConfig,
+    },
+    test_context,
+};
+use assert_matches::assert_matches;
+use codec::Encode;
+use sp_keyring::AccountKeyring;
+use sp_runtime::DispatchError;
+use subxt::{
+    extrinsic::PairSigner,
+    PalletError,
+    RuntimeError,
+};
+
+#[async_std::test]
+async fn tx_batch_transfers() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let calls = vec![
+        Call::Balances(balances::Call::transfer {
+            dest: AccountKeyring::Bob.to_account_id().into(),
+            value: 10_000,
+        }),
+        Call::Balances(balances::Call::transfer {
+            dest: AccountKeyring::Charlie.to_account_id().into(),
+            value: 10_000,
+        }),
+    ];
+
+    let result = cxt
+        .api
+        .tx()
+        .utility()
+        .batch(calls)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let completed = result.find_event::<utility::events::BatchCompleted>();
+    assert!(matches!(completed, Ok(Some(_))));
+    assert_eq!(result.batch_interrupted().unwrap(), None);
+}
+
+#[async_std::test]
+async fn tx_batch_interrupted() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let calls = vec![
+        Call::Balances(balances::Call::transfer {
+            dest: AccountKeyring::Bob.to_account_id().into(),
+            value: 10_000,
+        }),
+        Call::Balances(balances::Call::transfer {
+            dest: AccountKeyring::Charlie.to_account_id().into(),
+            value: u128::MAX,
+        }),
+    ];
+
+    let result = cxt
+        .api
+        .tx()
+        .utility()
+        .batch(calls)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let interrupted = result
+        .batch_interrupted()
+        .unwrap()
+        .expect("BatchInterrupted event should be present");
+    assert_eq!(interrupted.index, 1);
+}
+
+#[async_std::test]
+async fn tx_batch_interrupted_dispatch_error() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let cxt = test_context().await;
+
+    let calls = vec![Call::Balances(balances::Call::transfer {
+        dest: AccountKeyring::Bob.to_account_id().into(),
+        value: u128::MAX,
+    })];
+
+    let result = cxt
+        .api
+        .tx()
+        .utility()
+        .batch(calls)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let error = result
+        .dispatch_error()
+        .unwrap()
+        .expect("the failed transfer should be reported");
+    let metadata = cxt.client().metadata();
+    assert_eq!(
+        error,
+        DispatchError::Module {
+            index: metadata.pallet("Balances").unwrap().index(),
+            error: balances::Error::InsufficientBalance.encode()[0],
+            message: None,
+        }
+    );
+    assert_matches!(
+        RuntimeError::from_dispatch(metadata, error),
+        Ok(RuntimeError::Module(PalletError { pallet, error, .. }))
+            if pallet == "Balances" && error == "InsufficientBalance"
+    );
+}
@@ -21,7 +21,13 @@ mod utils;
 #[cfg(test)]
 mod client;
 #[cfg(test)]
+mod decode;
+#[cfg(test)]
+mod extra;
+#[cfg(test)]
 mod frame;
+#[cfg(test)]
+mod signer;
 
 pub use runtime::node_runtime;
 pub use utils::*;
@@ -16,7 +16,7 @@
 
 #[subxt::subxt(
     runtime_metadata_path = "tests/integration/node_runtime.scale",
-    generated_type_derives = "Debug, Eq, PartialEq"
+    generated_type_derives = "Clone, Debug, Eq, PartialEq"
 )]
 pub mod node_runtime {
     #[subxt(substitute_type = "sp_arithmetic::per_things::Perbill")]
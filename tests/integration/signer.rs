@@ -0,0 +1,463 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+    fixture_metadata,
+    node_runtime::{
+        balances,
+        system,
+        DefaultConfig,
+        RuntimeApi,
+    },
+    test_context,
+};
+use assert_matches::assert_matches;
+use codec::{
+    Decode,
+    Encode,
+};
+use sp_core::{
+    crypto::DEV_PHRASE,
+    ecdsa,
+    keccak_256,
+    sr25519,
+    Pair,
+};
+use sp_keyring::{
+    AccountKeyring,
+    Ed25519Keyring,
+};
+use sp_runtime::{
+    traits::{
+        BlakeTwo256,
+        Hash as _,
+        IdentifyAccount,
+        Keccak256,
+        Verify,
+    },
+    AccountId32,
+    MultiAddress,
+    MultiSignature,
+    MultiSigner,
+};
+use subxt::{
+    extrinsic::{
+        create_signed_offline,
+        AccountId20,
+        EcdsaSigner,
+        EthereumSignature,
+        OfflineParams,
+        PairSigner,
+        SignedPayload,
+        Signer,
+        UncheckedExtrinsic,
+    },
+    ClientBuilder,
+    Config,
+    DefaultExtra,
+    Encoded,
+    Error,
+    ExtrinsicExtraData,
+    SignedExtra,
+    SignedExtrinsic,
+};
+
+/// Config for a chain with Ethereum-style 20 byte accounts.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct EthereumConfig;
+
+impl Config for EthereumConfig {
+    type Index = u32;
+    type BlockNumber = u32;
+    type Hash = sp_core::H256;
+    type Hashing = sp_runtime::traits::BlakeTwo256;
+    type AccountId = AccountId20;
+    type Address = AccountId20;
+    type Header = sp_runtime::generic::Header<Self::BlockNumber, Self::Hashing>;
+    type Signature = EthereumSignature;
+    type Extrinsic = sp_runtime::OpaqueExtrinsic;
+}
+
+impl ExtrinsicExtraData<EthereumConfig> for EthereumConfig {
+    type Extra = DefaultExtra<EthereumConfig>;
+}
+
+/// Config for a chain hashing with Keccak rather than Blake2.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct KeccakConfig;
+
+impl Config for KeccakConfig {
+    type Index = u32;
+    type BlockNumber = u32;
+    type Hash = sp_core::H256;
+    type Hashing = Keccak256;
+    type AccountId = AccountId32;
+    type Address = MultiAddress<Self::AccountId, u32>;
+    type Header = sp_runtime::generic::Header<Self::BlockNumber, Self::Hashing>;
+    type Signature = MultiSignature;
+    type Extrinsic = sp_runtime::OpaqueExtrinsic;
+}
+
+impl ExtrinsicExtraData<KeccakConfig> for KeccakConfig {
+    type Extra = DefaultExtra<KeccakConfig>;
+}
+
+/// Config of the test node types, with an address type of our own.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct AccountAddressConfig;
+
+impl Config for AccountAddressConfig {
+    type Index = u32;
+    type BlockNumber = u32;
+    type Hash = sp_core::H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId32;
+    type Address = AccountAddress;
+    type Header = sp_runtime::generic::Header<Self::BlockNumber, Self::Hashing>;
+    type Signature = MultiSignature;
+    type Extrinsic = sp_runtime::OpaqueExtrinsic;
+}
+
+impl ExtrinsicExtraData<AccountAddressConfig> for AccountAddressConfig {
+    type Extra = DefaultExtra<AccountAddressConfig>;
+}
+
+/// An address which can only be an account id, encoded like the `Id` variant of the
+/// `MultiAddress` of the test node.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+enum AccountAddress {
+    #[codec(index = 0)]
+    Id(AccountId32),
+}
+
+impl From<AccountId32> for AccountAddress {
+    fn from(account_id: AccountId32) -> Self {
+        Self::Id(account_id)
+    }
+}
+
+/// The well known "Alith" development account.
+fn alith() -> (ecdsa::Pair, AccountId20) {
+    let seed =
+        hex::decode("5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133")
+            .unwrap();
+    let mut account = [0u8; 20];
+    account.copy_from_slice(
+        &hex::decode("f24ff3a9cf04c71dbc94d0b566f7a27b94566cac").unwrap(),
+    );
+    (ecdsa::Pair::from_seed_slice(&seed).unwrap(), account.into())
+}
+
+#[async_std::test]
+async fn tx_transfer_from_ecdsa_account() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let ecdsa_signer = PairSigner::<DefaultConfig, _>::new(
+        ecdsa::Pair::from_string("//Alice", None).unwrap(),
+    );
+    let cxt = test_context().await;
+
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(ecdsa_signer.account_id().clone(), 1_000_000_000_000_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .sign_and_submit_then_watch(&ecdsa_signer)
+        .await
+        .unwrap();
+    assert!(result
+        .find_event::<system::events::ExtrinsicSuccess>()
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn ethereum_signature_verifies_against_account() {
+    let (pair, account) = alith();
+    let message = b"payload";
+    let signature = EthereumSignature::from(pair.sign_prehashed(&keccak_256(message)));
+
+    assert!(signature.verify(&message[..], &account));
+    assert!(!signature.verify(&message[..], &AccountId20::default()));
+}
+
+#[test]
+fn ethereum_account_from_invalid_public_key_fails() {
+    let (pair, account) = alith();
+    assert_eq!(AccountId20::try_from(pair.public()).unwrap(), account);
+
+    // not a point on the curve
+    let public = ecdsa::Public::from_raw([0u8; 33]);
+    assert!(AccountId20::try_from(public).is_err());
+}
+
+#[async_std::test]
+async fn ecdsa_signer_signs_for_ethereum_account() {
+    let (pair, account) = alith();
+    let signer = EcdsaSigner::<EthereumConfig>::new(pair).unwrap();
+    assert_eq!(signer.account_id(), &account);
+
+    let extra = DefaultExtra::<EthereumConfig>::new(
+        1,
+        1,
+        0,
+        Default::default(),
+        Default::default(),
+    );
+    let call = Encoded(vec![0, 1, 2]);
+    let payload =
+        SignedPayload::<EthereumConfig>::new(call.clone(), extra.extra()).unwrap();
+    let extrinsic = signer.sign(payload).await.unwrap();
+
+    let (address, signature, _) = extrinsic.signature.unwrap();
+    assert_eq!(address, account);
+    let payload = SignedPayload::<EthereumConfig>::new(call, extra.extra()).unwrap();
+    assert!(payload.using_encoded(|payload| signature.verify(payload, &account)));
+}
+
+#[async_std::test]
+async fn tx_transfer_from_ethereum_config() {
+    let cxt = test_context().await;
+    let api: RuntimeApi<EthereumConfig> = ClientBuilder::new()
+        .set_url(cxt.node_proc.ws_url())
+        .build()
+        .await
+        .unwrap()
+        .to_runtime_api();
+    let (pair, account) = alith();
+    let mut signer = EcdsaSigner::<EthereumConfig>::new(pair).unwrap();
+    // the test node can't look up the nonce of a 20 byte account
+    signer.set_nonce(0);
+    let baltathar = AccountId20::from([0x3c; 20]);
+
+    // the test node only knows 32 byte accounts, so the extrinsic is only signed
+    let extrinsic = api
+        .tx()
+        .balances()
+        .transfer(baltathar, 10_000)
+        .create_signed(&signer)
+        .await
+        .unwrap();
+    let (address, _, _) = extrinsic.signature.as_ref().unwrap();
+    assert_eq!(address, &account);
+    assert_eq!(address.encode().len(), 20);
+    // the destination is encoded as the 20 byte address of the config
+    let call = balances::calls::Transfer {
+        dest: baltathar,
+        value: 10_000,
+    };
+    assert!(extrinsic.function.0.ends_with(&call.encode()));
+}
+
+#[async_std::test]
+async fn tx_transfer_with_custom_address_config() {
+    let cxt = test_context().await;
+    let api: RuntimeApi<AccountAddressConfig> = ClientBuilder::new()
+        .set_url(cxt.node_proc.ws_url())
+        .build()
+        .await
+        .unwrap()
+        .to_runtime_api();
+    let alice = PairSigner::<AccountAddressConfig, _>::new(AccountKeyring::Alice.pair());
+    let bob = AccountKeyring::Bob.to_account_id();
+
+    let result = api
+        .tx()
+        .balances()
+        .transfer(AccountAddress::Id(bob.clone()), 10_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+    let transfer = result.find_event::<balances::events::Transfer>().unwrap();
+    assert_eq!(
+        transfer,
+        Some(balances::events::Transfer(
+            AccountKeyring::Alice.to_account_id(),
+            bob,
+            10_000
+        ))
+    );
+}
+
+/// A signer which signs asynchronously, like a remote signing service would.
+struct MockAsyncSigner {
+    inner: PairSigner<DefaultConfig, sp_core::sr25519::Pair>,
+    refuse: bool,
+}
+
+#[async_trait::async_trait]
+impl Signer<DefaultConfig> for MockAsyncSigner {
+    fn account_id(&self) -> &<DefaultConfig as Config>::AccountId {
+        self.inner.account_id()
+    }
+
+    fn nonce(&self) -> Option<<DefaultConfig as Config>::Index> {
+        self.inner.nonce()
+    }
+
+    async fn sign(
+        &self,
+        extrinsic: SignedPayload<DefaultConfig>,
+    ) -> Result<UncheckedExtrinsic<DefaultConfig>, String> {
+        // simulate the round trip to the remote signer
+        futures_timer::Delay::new(std::time::Duration::from_millis(50)).await;
+        if self.refuse {
+            return Err("Signing refused by remote signer".into())
+        }
+        self.inner.sign(extrinsic).await
+    }
+}
+
+#[async_std::test]
+async fn tx_transfer_with_async_signer() {
+    let signer = MockAsyncSigner {
+        inner: PairSigner::new(AccountKeyring::Alice.pair()),
+        refuse: false,
+    };
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .sign_and_submit_then_watch(&signer)
+        .await
+        .unwrap();
+    assert!(result
+        .find_event::<system::events::ExtrinsicSuccess>()
+        .unwrap()
+        .is_some());
+}
+
+#[async_std::test]
+async fn tx_transfer_with_refusing_async_signer() {
+    let signer = MockAsyncSigner {
+        inner: PairSigner::new(AccountKeyring::Alice.pair()),
+        refuse: true,
+    };
+    let cxt = test_context().await;
+
+    let result = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .sign_and_submit_then_watch(&signer)
+        .await;
+    assert_matches!(result, Err(Error::Other(message)) if message.contains("refused"));
+}
+
+#[async_std::test]
+async fn tx_transfer_from_ed25519_account() {
+    let alice = PairSigner::<DefaultConfig, _>::new(AccountKeyring::Alice.pair());
+    let ed25519_pair = Ed25519Keyring::Alice.pair();
+    let ed25519_signer = PairSigner::<DefaultConfig, _>::new(ed25519_pair.clone());
+    let ed25519_account = MultiSigner::from(ed25519_pair.public()).into_account();
+    assert_eq!(
+        Signer::<DefaultConfig>::account_id(&ed25519_signer),
+        &ed25519_account
+    );
+    let cxt = test_context().await;
+
+    // endow the ed25519 account, which is not endowed at genesis
+    cxt.api
+        .tx()
+        .balances()
+        .transfer(ed25519_account, 1_000_000_000_000_000)
+        .sign_and_submit_then_watch(&alice)
+        .await
+        .unwrap();
+
+    let extrinsic = cxt
+        .api
+        .tx()
+        .balances()
+        .transfer(AccountKeyring::Bob.to_account_id(), 10_000)
+        .create_signed(&ed25519_signer)
+        .await
+        .unwrap();
+    assert_matches!(
+        extrinsic.signature,
+        Some((_, MultiSignature::Ed25519(_), _))
+    );
+
+    let result = cxt
+        .client()
+        .rpc()
+        .submit_and_watch_extrinsic(extrinsic, cxt.client().events_decoder())
+        .await
+        .unwrap();
+    assert!(result
+        .find_event::<system::events::ExtrinsicSuccess>()
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+fn pair_signer_from_seed_and_phrase() {
+    let alice = AccountKeyring::Alice.to_account_id();
+
+    let seed: [u8; 32] =
+        hex::decode("e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let signer = PairSigner::<DefaultConfig, sr25519::Pair>::from_seed(&seed);
+    assert_eq!(Signer::<DefaultConfig>::account_id(&signer), &alice);
+
+    let phrase = format!("{}//Alice", DEV_PHRASE);
+    let signer =
+        PairSigner::<DefaultConfig, sr25519::Pair>::from_phrase(&phrase, None).unwrap();
+    assert_eq!(Signer::<DefaultConfig>::account_id(&signer), &alice);
+
+    assert_matches!(
+        PairSigner::<DefaultConfig, sr25519::Pair>::from_phrase("not a phrase", None),
+        Err(Error::SecretString(_))
+    );
+}
+
+#[async_std::test]
+async fn extrinsic_hash_uses_hashing_of_config() {
+    let metadata = fixture_metadata();
+    let alice = PairSigner::<KeccakConfig, _>::new(AccountKeyring::Alice.pair());
+    let params = OfflineParams {
+        spec_version: 1,
+        tx_version: 1,
+        genesis_hash: Default::default(),
+        nonce: 0,
+        extrinsic_params: Default::default(),
+    };
+
+    let call: balances::calls::Transfer = balances::calls::Transfer {
+        dest: AccountKeyring::Bob.to_account_id().into(),
+        value: 10_000,
+    };
+    let extrinsic = create_signed_offline(&metadata, &call, &alice, params)
+        .await
+        .unwrap();
+
+    let encoded = extrinsic.encoded().0;
+    assert_eq!(extrinsic.hash(), keccak_256(&encoded).into());
+    assert_ne!(extrinsic.hash(), BlakeTwo256::hash(&encoded));
+}
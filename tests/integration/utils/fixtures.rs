@@ -0,0 +1,63 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::node_runtime::Event;
+use codec::{
+    Decode,
+    Encode,
+};
+use frame_metadata::RuntimeMetadataPrefixed;
+use subxt::{
+    Metadata,
+    Phase,
+};
+
+/// The encoded metadata of the test node, which the runtime api is generated from.
+pub const METADATA_BYTES: &[u8] = include_bytes!("../node_runtime.scale");
+
+/// Decode the metadata of the test node, for tests which don't need a running node.
+pub fn fixture_metadata() -> Metadata {
+    RuntimeMetadataPrefixed::decode(&mut &METADATA_BYTES[..])
+        .expect("the fixture metadata should decode")
+        .try_into()
+        .expect("the fixture metadata should be V14")
+}
+
+/// Encode events the way the `System::Events` storage holds them, as records without
+/// topics.
+pub fn encode_event_records(
+    records: impl IntoIterator<Item = (Phase, Event)>,
+) -> Vec<u8> {
+    records
+        .into_iter()
+        .map(|(phase, event)| {
+            EventRecord {
+                phase,
+                event,
+                topics: Vec::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .encode()
+}
+
+/// The encoding of an event record in the `System::Events` storage.
+#[derive(Encode)]
+struct EventRecord {
+    phase: Phase,
+    event: Event,
+    topics: Vec<sp_core::H256>,
+}
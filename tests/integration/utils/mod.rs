@@ -15,7 +15,11 @@
 // along with subxt.  If not, see <http://www.gnu.org/licenses/>.
 
 mod context;
+mod fixtures;
 mod node_proc;
+mod proxy;
 
 pub use context::*;
+pub use fixtures::*;
 pub use node_proc::TestNodeProcess;
+pub use proxy::TestProxy;
@@ -39,6 +39,8 @@ use subxt::{
 pub struct TestNodeProcess<R: Config> {
     proc: process::Child,
     client: Client<R>,
+    ws_url: String,
+    http_url: String,
 }
 
 impl<R> Drop for TestNodeProcess<R>
@@ -81,6 +83,16 @@ where
     pub fn client(&self) -> &Client<R> {
         &self.client
     }
+
+    /// Returns the url of the WebSocket rpc endpoint of the running node.
+    pub fn ws_url(&self) -> &str {
+        &self.ws_url
+    }
+
+    /// Returns the url of the HTTP rpc endpoint of the running node.
+    pub fn http_url(&self) -> &str {
+        &self.http_url
+    }
 }
 
 /// Construct a test node process.
@@ -130,20 +142,21 @@ impl TestNodeProcessBuilder {
             cmd.arg(arg);
         }
 
-        let ws_port = if self.scan_port_range {
+        let (http_port, ws_port) = if self.scan_port_range {
             let (p2p_port, http_port, ws_port) = next_open_port()
                 .ok_or("No available ports in the given port range".to_owned())?;
 
             cmd.arg(format!("--port={}", p2p_port));
             cmd.arg(format!("--rpc-port={}", http_port));
             cmd.arg(format!("--ws-port={}", ws_port));
-            ws_port
+            (http_port, ws_port)
         } else {
-            // the default Websockets port
-            9944
+            // the default HTTP and Websockets ports
+            (9933, 9944)
         };
 
         let ws_url = format!("ws://127.0.0.1:{}", ws_port);
+        let http_url = format!("http://127.0.0.1:{}", http_port);
 
         let mut proc = cmd.spawn().map_err(|e| {
             format!(
@@ -177,7 +190,14 @@ impl TestNodeProcessBuilder {
             }
         };
         match client {
-            Ok(client) => Ok(TestNodeProcess { proc, client }),
+            Ok(client) => {
+                Ok(TestNodeProcess {
+                    proc,
+                    client,
+                    ws_url,
+                    http_url,
+                })
+            }
             Err(err) => {
                 let err = format!(
                     "Failed to connect to node rpc at {} after {} attempts: {}",
@@ -0,0 +1,200 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{
+    io::{
+        Read,
+        Write,
+    },
+    net::{
+        Shutdown,
+        TcpListener,
+        TcpStream,
+    },
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+/// TCP proxy in front of a node's rpc endpoint, for simulating dropped connections and
+/// unresponsive nodes, and for inspecting the messages sent to the node.
+///
+/// The proxy forwards on threads of its own, so that it works with tests on any async
+/// runtime.
+pub struct TestProxy {
+    port: u16,
+    connections: Arc<Mutex<Vec<TcpStream>>>,
+    stalled: Arc<AtomicBool>,
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestProxy {
+    /// Start proxying connections on a free local port to the given address.
+    pub fn start(target: String) -> TestProxy {
+        let listener =
+            TcpListener::bind("127.0.0.1:0").expect("Failed to bind proxy listener");
+        let port = listener.local_addr().unwrap().port();
+        let connections = Arc::new(Mutex::new(Vec::new()));
+        let proxy_connections = connections.clone();
+        let stalled = Arc::new(AtomicBool::new(false));
+        let proxy_stalled = stalled.clone();
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let proxy_messages = messages.clone();
+        thread::spawn(move || {
+            for inbound in listener.incoming() {
+                let inbound = match inbound {
+                    Ok(inbound) => inbound,
+                    Err(_) => continue,
+                };
+                let outbound = match TcpStream::connect(&target) {
+                    Ok(outbound) => outbound,
+                    Err(_) => continue,
+                };
+                proxy_connections.lock().unwrap().extend(vec![
+                    inbound.try_clone().unwrap(),
+                    outbound.try_clone().unwrap(),
+                ]);
+                forward(
+                    inbound.try_clone().unwrap(),
+                    outbound.try_clone().unwrap(),
+                    proxy_stalled.clone(),
+                    Some(proxy_messages.clone()),
+                );
+                forward(outbound, inbound, proxy_stalled.clone(), None);
+            }
+        });
+        TestProxy {
+            port,
+            connections,
+            stalled,
+            messages,
+        }
+    }
+
+    /// Returns the WebSocket url of the proxy.
+    pub fn ws_url(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.port)
+    }
+
+    /// Drop all connections currently open through the proxy.
+    pub fn drop_connections(&self) {
+        for connection in self.connections.lock().unwrap().drain(..) {
+            let _ = connection.shutdown(Shutdown::Both);
+        }
+    }
+
+    /// Returns the WebSocket text messages sent to the node through the proxy, in order.
+    pub fn sent_messages(&self) -> Vec<String> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    /// While stalled, the data sent through the proxy is held back, so that requests time
+    /// out.
+    pub fn set_stalled(&self, stalled: bool) {
+        self.stalled.store(stalled, Ordering::SeqCst);
+    }
+}
+
+/// Copy everything read from one stream to the other, until either is closed.
+///
+/// If `messages` is given, the text messages of the WebSocket frames read are recorded.
+fn forward(
+    mut from: TcpStream,
+    mut to: TcpStream,
+    stalled: Arc<AtomicBool>,
+    messages: Option<Arc<Mutex<Vec<String>>>>,
+) {
+    thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        let mut frames = Vec::new();
+        let mut handshake_done = false;
+        while let Ok(len) = from.read(&mut buf) {
+            while stalled.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(10));
+            }
+            if len == 0 || to.write_all(&buf[..len]).is_err() {
+                break
+            }
+            if let Some(messages) = &messages {
+                frames.extend_from_slice(&buf[..len]);
+                if !handshake_done {
+                    // the frames follow the HTTP upgrade request
+                    match frames.windows(4).position(|w| w == b"\r\n\r\n") {
+                        Some(end) => {
+                            frames.drain(..end + 4);
+                            handshake_done = true;
+                        }
+                        None => continue,
+                    }
+                }
+                record_messages(&mut frames, messages);
+            }
+        }
+        let _ = to.shutdown(Shutdown::Both);
+    });
+}
+
+/// Record the text messages of the complete WebSocket frames at the start of `frames`, and
+/// remove those frames.
+fn record_messages(frames: &mut Vec<u8>, messages: &Mutex<Vec<String>>) {
+    while frames.len() >= 2 {
+        let opcode = frames[0] & 0x0f;
+        let masked = frames[1] & 0x80 != 0;
+        let (len, mut offset) = match frames[1] & 0x7f {
+            126 if frames.len() >= 4 => {
+                (u16::from_be_bytes([frames[2], frames[3]]) as usize, 4)
+            }
+            127 if frames.len() >= 10 => {
+                let mut len = [0u8; 8];
+                len.copy_from_slice(&frames[2..10]);
+                (u64::from_be_bytes(len) as usize, 10)
+            }
+            126 | 127 => return,
+            len => (len as usize, 2),
+        };
+        let mut mask = [0u8; 4];
+        if masked {
+            if frames.len() < offset + 4 {
+                return
+            }
+            mask.copy_from_slice(&frames[offset..offset + 4]);
+            offset += 4;
+        }
+        if frames.len() < offset + len {
+            return
+        }
+        let payload: Vec<u8> = frames[offset..offset + len]
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ mask[i % 4])
+            .collect();
+        // only text frames, the client sends its messages in single frames
+        if opcode == 1 {
+            messages
+                .lock()
+                .unwrap()
+                .push(String::from_utf8_lossy(&payload).into_owned());
+        }
+        frames.drain(..offset + len);
+    }
+}